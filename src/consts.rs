@@ -2,13 +2,21 @@ use super::*;
 
 pub const SCALE_LIMIT: f32 = 10.;
 pub const SCROLL_SENSITIVITY: Point = Point { x: 30., y: -30. };
+pub const SCROLL_FRICTION: f32 = 8.;
+pub const CAMERA_DURATION: Duration = Duration::from_millis(200);
+pub const WATCH_INTERVAL: Duration = Duration::from_millis(500);
 pub const DEBUG_HITBOXES: bool = true;
-pub const KEYBOARD_DEBOUNCE: Duration = Duration::from_millis(100);
-pub const ARTPACK: &'static str = "/tiles.png";
-pub const ARTPACK_WIDTH: f32 = 512.;
-pub const ARTPACK_HEIGHT: f32 = 512.;
-pub const TILE_WIDTH: f32 = 16.;
-pub const TILE_HEIGHT: f32 = 16.;
+pub const KEYBOARD_REPEAT_DELAY: Duration = Duration::from_millis(400);
+pub const KEYBOARD_REPEAT_RATE: Duration = Duration::from_millis(50);
+pub const TILESET: &'static str = "tiles";
 pub const FPS: f32 = 30.;
 pub const RATE: f32 = 1.0 / FPS;
 pub const DEADZONE: f32 = 0.11;
+pub const GAMEPAD_CURSOR_SPEED: f32 = 0.25;
+pub const GAMEPAD_PAN_SPEED: f32 = 20.;
+pub const GAMEPAD_ZOOM_SPEED: f32 = 0.05;
+pub const PREFABS: &'static str = "prefabs";
+pub const PANEL_WIDTH: f32 = 320.;
+pub const KEYMAP: &'static str = "keymap.txt";
+pub const SCENE: &'static str = "scene.txt";
+pub const GRID: &'static str = "grid.txt";
@@ -0,0 +1,139 @@
+// Actions of the pencil scope only apply while painting, those of the
+// selection scope while selecting or pasting
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Scope {
+    Global,
+    Pencil,
+    Selection,
+}
+
+impl Scope {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Global => "Global",
+            Self::Pencil => "Pencil",
+            Self::Selection => "Selection",
+        }
+    }
+
+    pub fn overlaps(&self, other: Self) -> bool {
+        *self == other || *self == Self::Global || other == Self::Global
+    }
+}
+
+macro_rules! repeat {
+    (repeat) => {
+        true
+    };
+}
+
+// Actions marked with `repeat` trigger again while their chord is held
+macro_rules! actions {
+    ($($Scope:ident [$($Action:ident $description:literal [$($chord:literal)* $(; $repeat:ident)?])*])*) => {
+        #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+        pub enum Action { $($($Action,)*)* }
+
+        impl Action {
+            pub const fn all() -> &'static [Self] {
+                &[$($(Self::$Action,)*)*]
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $($(Self::$Action => stringify!($Action),)*)*
+                }
+            }
+
+            pub fn from_name(name: &str) -> Option<Self> {
+                Self::all().iter().copied().find(|action| action.name() == name)
+            }
+
+            pub fn description(&self) -> &'static str {
+                match self {
+                    $($(Self::$Action => $description,)*)*
+                }
+            }
+
+            pub fn scope(&self) -> Scope {
+                match self {
+                    $($(Self::$Action => Scope::$Scope,)*)*
+                }
+            }
+
+            pub fn repeats(&self) -> bool {
+                match self {
+                    $($(Self::$Action => false $(|| repeat!($repeat))?,)*)*
+                }
+            }
+
+            pub fn defaults(&self) -> &'static [&'static str] {
+                match self {
+                    $($(Self::$Action => &[$($chord,)*],)*)*
+                }
+            }
+        }
+    };
+}
+
+actions!(
+    Global [
+        // Editor
+        SwitchView       "Switch between scene and palette" ["Tab"]
+        TogglePanel      "Toggle the palette panel"         ["P"]
+        RandomBackground "Toggle a random background"       ["B"]
+        Help             "Toggle this help"                 ["F1"]
+        Commands         "Search and run commands"          ["Ctrl+Shift+P"]
+        Save             "Save the scene"                   ["Ctrl+S"]
+        // Viewport
+        Origin           "Go back to the origin"            ["Key0"]
+        ZoomReset        "Reset the zoom"                   ["Equals"]
+        // Plus is typed with Shift+Equals on US layouts
+        ZoomIn           "Zoom in"                          ["Plus" "Shift+Equals" "PageUp"; repeat]
+        ZoomOut          "Zoom out"                         ["Minus" "PageDown"; repeat]
+        ZoomFit          "Zoom to fit the scene"            ["Ctrl+Key0"]
+        ZoomSelection    "Zoom to fit the selection"        ["Shift+Key0"]
+        ToggleSnap       "Snap the zoom to whole pixels"    ["Ctrl+Equals"]
+        ToggleSplit      "Split the scene in two panes"     ["Backslash"]
+        PanUp            "Pan up"                           ["Up"; repeat]
+        PanDown          "Pan down"                         ["Down"; repeat]
+        PanLeft          "Pan left"                         ["Left"; repeat]
+        PanRight         "Pan right"                        ["Right"; repeat]
+        PanScreenUp      "Pan a screen up"                  ["Ctrl+Up"]
+        PanScreenDown    "Pan a screen down"                ["Ctrl+Down"]
+        PanScreenLeft    "Pan a screen left"                ["Ctrl+Left"]
+        PanScreenRight   "Pan a screen right"               ["Ctrl+Right"]
+        // The nth chord goes to, or sets, the nth bookmark
+        Bookmark         "Go to a bookmark"                 ["Key1" "Key2" "Key3" "Key4" "Key5" "Key6" "Key7" "Key8" "Key9"]
+        SetBookmark      "Set a bookmark"                   ["Ctrl+Key1" "Ctrl+Key2" "Ctrl+Key3" "Ctrl+Key4" "Ctrl+Key5" "Ctrl+Key6" "Ctrl+Key7" "Ctrl+Key8" "Ctrl+Key9"]
+        // Show
+        ToggleFloors     "Show or hide floors"              ["F"]
+        ToggleWalls      "Show or hide walls"               ["W"]
+        ToggleGrid       "Show or hide the grid"            ["G"]
+        ToggleMinimap    "Show or hide the minimap"         ["M"]
+        // History
+        Undo             "Undo"                             ["Ctrl+Z"; repeat]
+        Redo             "Redo"                             ["Ctrl+Shift+Z"; repeat]
+    ]
+    Pencil [
+        RotateRight      "Rotate the pencil clockwise"      ["R"]
+        RotateLeft       "Rotate the pencil anticlockwise"  ["Shift+R"]
+        Replace          "Replace the hovered element"      ["Ctrl+H"]
+        ReplaceOriented  "Replace, matching orientations"   ["Ctrl+Shift+H"]
+        ToggleLinked     "Toggle linked prefab stamping"    ["L"]
+    ]
+    Selection [
+        Rotate90         "Rotate clockwise"                 ["R"]
+        Rotate180        "Rotate half a turn"               ["Ctrl+R"]
+        Rotate270        "Rotate anticlockwise"             ["Shift+R"]
+        MirrorHorizontal "Mirror horizontally"              ["H"]
+        MirrorVertical   "Mirror vertically"                ["V"]
+        SelectAll        "Select all"                       ["Ctrl+A"]
+        InvertSelection  "Invert the selection"             ["Ctrl+I"]
+        Delete           "Delete the selection"             ["Delete"]
+        Cut              "Cut the selection"                ["Ctrl+X"]
+        Copy             "Copy the selection"               ["Ctrl+C"]
+        Paste            "Paste"                            ["Ctrl+V"]
+        CancelPaste      "Cancel pasting"                   ["Escape"]
+        SavePrefab       "Save the selection as a prefab"   ["Ctrl+P"]
+    ]
+);
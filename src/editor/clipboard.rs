@@ -0,0 +1,136 @@
+use super::*;
+use copypasta::{ClipboardContext, ClipboardProvider};
+use std::fmt;
+
+// The system clipboard, opened on first use and kept open, since on X11 and
+// Wayland the copied text is served by its owner and goes away with it. It is
+// left alone while the inputs are replayed or recorded, so that replays only
+// depend on the frames
+#[derive(Clone)]
+pub struct Clipboard {
+    context: Option<Rc<RefCell<ClipboardContext>>>,
+    system:  bool,
+}
+
+impl fmt::Debug for Clipboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Clipboard")
+            .field("open", &self.context.is_some())
+            .field("system", &self.system)
+            .finish()
+    }
+}
+
+impl Clipboard {
+    const HEADER: &'static str = "rpg-scene";
+
+    pub fn new() -> Self {
+        Self {
+            context: None,
+            system:  true,
+        }
+    }
+
+    pub fn set_system(&mut self, system: bool) {
+        self.system = system;
+    }
+
+    fn with<T>(&mut self, f: impl FnOnce(&mut ClipboardContext) -> Option<T>) -> Option<T> {
+        if !self.system {
+            return None;
+        }
+
+        if self.context.is_none() {
+            self.context = ClipboardContext::new()
+                .ok()
+                .map(|context| Rc::new(RefCell::new(context)));
+        }
+
+        f(&mut self.context.as_ref()?.borrow_mut())
+    }
+
+    // The text of an empty selection would not be pasted back, the clipboard
+    // is left as it is
+    pub fn copy(&mut self, (cells, scene): &(Cells, Scene)) {
+        if cells.is_empty() {
+            return;
+        }
+
+        let text = Self::to_text(cells, scene);
+
+        self.with(|clipboard| clipboard.set_contents(text).ok());
+    }
+
+    pub fn paste(&mut self) -> Option<(Cells, Scene)> {
+        let text = self.with(|clipboard| clipboard.get_contents().ok())?;
+
+        Self::from_text(&text)
+    }
+
+    // The selection line holds the bounds of the cells, which are listed
+    // afterwards
+    pub fn to_text(cells: &Cells, scene: &Scene) -> String {
+        let selection = match cells.bounds().map(ButtonSelection::from_ranges) {
+            Some(ButtonSelection::Start(Point { x, y })) => format!("selection {} {}", x, y),
+            Some(ButtonSelection::Select((start, end))) =>
+                format!("selection {} {} {} {}", start.x, start.y, end.x, end.y),
+            None => String::from("selection"),
+        };
+
+        let mut cells = cells.iter().collect::<Vec<_>>();
+        cells.sort_by_key(|cell| (cell.y, cell.x));
+        let cells = cells
+            .iter()
+            .map(|Point { x, y }| format!("cell {} {}\n", x, y))
+            .collect::<String>();
+
+        format!(
+            "{}\n{}\n{}{}",
+            Self::HEADER,
+            selection,
+            cells,
+            scene.to_text()
+        )
+    }
+
+    // Without cell lines, the whole selection is used
+    pub fn from_text(text: &str) -> Option<(Cells, Scene)> {
+        let mut lines = text.lines();
+
+        if lines.next()?.trim() != Self::HEADER {
+            return None;
+        }
+
+        let mut selection = lines.next()?.split_whitespace();
+        if selection.next()? != "selection" {
+            return None;
+        }
+
+        let selection = selection
+            .map(|coordinate| coordinate.parse().ok())
+            .collect::<Option<Vec<f32>>>()?;
+        let selection = match selection.as_slice() {
+            &[x, y] => ButtonSelection::Start(Point { x, y }),
+            &[sx, sy, ex, ey] =>
+                ButtonSelection::Select((Point { x: sx, y: sy }, Point { x: ex, y: ey })),
+            _ => return None,
+        };
+
+        let mut cells = Vec::new();
+        for line in lines {
+            if let ["cell", x, y] = line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                cells.push(Point {
+                    x: x.parse().ok()?,
+                    y: y.parse().ok()?,
+                });
+            }
+        }
+        let cells = if cells.is_empty() {
+            Cells::from_ranges(selection.ranges())
+        } else {
+            cells.into_iter().collect()
+        };
+
+        Some((cells, Scene::from_text(text)?))
+    }
+}
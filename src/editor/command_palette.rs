@@ -0,0 +1,127 @@
+use super::*;
+
+#[derive(Clone, Debug)]
+pub struct CommandPalette {
+    input:    TextInput,
+    selected: usize,
+}
+
+impl CommandPalette {
+    const LINE: f32 = 20.;
+    const LINES: usize = 15;
+    const WIDTH: f32 = 640.;
+
+    pub fn new() -> Self {
+        Self {
+            input:    TextInput::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn events(&mut self, keyboard: &Keyboard) {
+        let input = self.input.text().to_string();
+        self.input.events(keyboard);
+
+        if self.input.text() != input {
+            self.selected = 0;
+        } else if keyboard.is_repeated(KeyCode::Down) {
+            self.selected = (self.selected + 1).min(self.matches().len().saturating_sub(1));
+        } else if keyboard.is_repeated(KeyCode::Up) {
+            self.selected = self.selected.saturating_sub(1);
+        }
+    }
+
+    pub fn selected(&self) -> Option<Action> {
+        self.matches().get(self.selected).copied()
+    }
+
+    // Best matches first
+    fn matches(&self) -> Vec<Action> {
+        let input = self.input.text();
+        let mut matches = Action::all()
+            .iter()
+            .filter(|&&action| action != Action::Commands)
+            .filter_map(|&action| {
+                let name = fuzzy(input, action.name());
+                let description = fuzzy(input, action.description());
+
+                Some((name.max(description)?, action))
+            })
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+
+        matches.into_iter().map(|(_, action)| action).collect()
+    }
+
+    pub fn draw(&self, ctx: &mut Context, keymap: &Keymap) {
+        let (w, _) = drawable_size(ctx);
+        let matches = self.matches();
+        let first = (self.selected + 1).saturating_sub(Self::LINES);
+        let lines = matches.iter().skip(first).take(Self::LINES);
+        let x = (w - Self::WIDTH) / 2.;
+        let y = 60.;
+        let h = (lines.len() + 1) as f32 * Self::LINE + 10.;
+
+        let mut mesh = MeshBuilder::new();
+        mesh.rectangle(
+            DrawMode::fill(),
+            [x, y, Self::WIDTH, h].into(),
+            Color::new(0.1, 0.1, 0.1, 0.95),
+        )
+        .unwrap();
+        if !matches.is_empty() {
+            let selected = y + 5. + (self.selected - first + 1) as f32 * Self::LINE;
+            mesh.rectangle(
+                DrawMode::fill(),
+                [x, selected, Self::WIDTH, Self::LINE].into(),
+                Color::new(0.2, 0.3, 0.6, 1.),
+            )
+            .unwrap();
+        }
+        mesh.build(ctx)
+            .unwrap()
+            .draw(ctx, DrawParam::new())
+            .unwrap();
+
+        self.input.draw(ctx, "Command", [x + 10., y + 7.]);
+
+        for (i, action) in lines.enumerate() {
+            let chords = keymap
+                .chords(*action)
+                .iter()
+                .map(|chord| chord.name())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let y = y + 7. + (i + 1) as f32 * Self::LINE;
+
+            Text::new(format!("{} - {}", action.name(), action.description()))
+                .draw(ctx, DrawParam::new().dest([x + 10., y]))
+                .unwrap();
+            Text::new(chords)
+                .draw(ctx, DrawParam::new().dest([x + Self::WIDTH - 150., y]))
+                .unwrap();
+        }
+    }
+}
+
+// Characters of `pattern` must appear in order in `text`, consecutive ones
+// scoring higher
+fn fuzzy(pattern: &str, text: &str) -> Option<usize> {
+    let mut text = text.chars().map(|c| c.to_ascii_lowercase()).enumerate();
+    let mut score = 0;
+    let mut last = None;
+
+    for c in pattern.chars().filter(|c| !c.is_whitespace()) {
+        let c = c.to_ascii_lowercase();
+        let (i, _) = text.find(|&(_, t)| t == c)?;
+
+        score += if last.map(|last| last + 1) == Some(i) {
+            3
+        } else {
+            1
+        };
+        last = Some(i);
+    }
+
+    Some(score)
+}
@@ -0,0 +1,133 @@
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MenuItem {
+    Pick,
+    Delete,
+    Rotate,
+    Copy,
+    Fill,
+}
+
+impl MenuItem {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Pick => "Pick",
+            Self::Delete => "Delete",
+            Self::Rotate => "Rotate",
+            Self::Copy => "Copy",
+            Self::Fill => "Fill",
+        }
+    }
+}
+
+// Opened by a right click over `cell`, items being listed from `position`
+#[derive(Clone, Debug)]
+pub struct ContextMenu {
+    position: Point,
+    cell:     Point<i16>,
+    items:    Vec<MenuItem>,
+}
+
+impl ContextMenu {
+    const LINE: f32 = 20.;
+    const WIDTH: f32 = 120.;
+
+    // Kept inside `size`
+    pub fn new(position: Point, cell: Point<i16>, items: Vec<MenuItem>, size: Point) -> Self {
+        let h = items.len() as f32 * Self::LINE;
+        let position = Point {
+            x: position.x.min(size.x - Self::WIDTH).max(0.),
+            y: position.y.min(size.y - h).max(0.),
+        };
+
+        Self {
+            position,
+            cell,
+            items,
+        }
+    }
+
+    // Picking needs an element under the cursor, deleting, rotating and
+    // copying cells with content, and filling a floor or wall pencil
+    pub fn items(pick: bool, content: bool, fill: bool) -> Vec<MenuItem> {
+        let mut items = Vec::new();
+
+        if pick {
+            items.push(MenuItem::Pick);
+        }
+        if content {
+            items.extend([MenuItem::Delete, MenuItem::Rotate, MenuItem::Copy]);
+        }
+        if fill {
+            items.push(MenuItem::Fill);
+        }
+
+        items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn cell(&self) -> Point<i16> {
+        self.cell
+    }
+
+    // The middle of the item's line
+    pub fn item_position(&self, item: MenuItem) -> Option<Point> {
+        let i = self.items.iter().position(|&other| other == item)?;
+
+        Some(Point {
+            x: self.position.x + Self::WIDTH / 2.,
+            y: self.position.y + (i as f32 + 0.5) * Self::LINE,
+        })
+    }
+
+    pub fn item_at(&self, position: Point) -> Option<MenuItem> {
+        let Point { x, y } = self.position;
+
+        if position.x < x || position.x > x + Self::WIDTH || position.y < y {
+            return None;
+        }
+
+        self.items
+            .get(((position.y - y) / Self::LINE) as usize)
+            .copied()
+    }
+
+    pub fn draw(&self, ctx: &mut Context, mouse: &Mouse) {
+        let Point { x, y } = self.position;
+        let h = self.items.len() as f32 * Self::LINE;
+        let hovered = self.item_at(mouse.position());
+
+        let mut mesh = MeshBuilder::new();
+        mesh.rectangle(
+            DrawMode::fill(),
+            [x, y, Self::WIDTH, h].into(),
+            Color::new(0.1, 0.1, 0.1, 0.95),
+        )
+        .unwrap();
+        if let Some(i) = self.items.iter().position(|&item| Some(item) == hovered) {
+            mesh.rectangle(
+                DrawMode::fill(),
+                [x, y + i as f32 * Self::LINE, Self::WIDTH, Self::LINE].into(),
+                Color::new(0.2, 0.3, 0.6, 1.),
+            )
+            .unwrap();
+        }
+        mesh.build(ctx)
+            .unwrap()
+            .draw(ctx, DrawParam::new())
+            .unwrap();
+
+        for (i, item) in self.items.iter().enumerate() {
+            Text::new(item.name())
+                .draw(
+                    ctx,
+                    DrawParam::new().dest([x + 10., y + 3. + i as f32 * Self::LINE]),
+                )
+                .unwrap();
+        }
+    }
+}
@@ -19,35 +19,183 @@ impl Views {
 pub struct Editor {
     keyboard:      Keyboard,
     mouse:         Mouse,
+    gamepad:       Gamepad,
     scene_view:    SceneView,
     tiles_view:    TilesView,
-    tile_renderer: TileRenderer,
+    tile_renderer: Option<TileRenderer>,
+    catalogue:     Rc<Catalogue>,
     now:           Instant,
+    size:          Point,
+    time:          Duration,
     view:          Views,
+    panel:         bool,
+    help:          bool,
+    commands:      Option<CommandPalette>,
+    menu:          Option<ContextMenu>,
     background:    Color,
+    start:         Instant,
+    input:         Box<dyn InputSource>,
+    recorder:      Option<Recorder>,
 }
 
 impl Editor {
     pub fn new(ctx: &mut Context) -> Self {
+        let (w, h) = drawable_size(ctx);
+        let mut editor = Self::with_input([w, h].into(), LiveInput::new());
+        editor.keyboard.set_keymap(Keymap::load());
+        editor.scene_view.load_config();
+
+        editor
+    }
+
+    // Without a window (e.g. in tests), the tile renderer being created on the
+    // first draw, and the default tiles being used until then. The keymap,
+    // grid and prefabs are the defaults, whatever the working directory holds
+    pub fn with_input(size: Point, input: impl InputSource + 'static) -> Self {
         Self {
-            keyboard:      Keyboard::new(),
-            mouse:         Mouse::new(),
-            scene_view:    SceneView::new(ctx),
-            tiles_view:    TilesView::new(ctx),
-            tile_renderer: TileRenderer::new(ctx),
-            now:           Instant::now(),
-            view:          Views::Scene,
-            background:    Color::BLACK,
+            keyboard: Keyboard::new(),
+            mouse: Mouse::new(),
+            gamepad: Gamepad::new(),
+            scene_view: SceneView::new(size),
+            tiles_view: TilesView::new(size),
+            tile_renderer: None,
+            catalogue: Rc::new(Catalogue::default_tileset()),
+            now: Instant::now(),
+            size,
+            time: Duration::from_secs(0),
+            view: Views::Scene,
+            panel: true,
+            help: false,
+            commands: None,
+            menu: None,
+            background: Color::BLACK,
+            start: Instant::now(),
+            input: Box::new(input),
+            recorder: None,
+        }
+    }
+
+    pub fn open(&mut self, path: &str) {
+        self.scene_view.open(path);
+    }
+
+    pub fn scene(&self) -> &Scene {
+        self.scene_view.scene()
+    }
+
+    pub fn viewport(&self) -> &Viewport {
+        self.scene_view.viewport()
+    }
+
+    pub fn panes(&self) -> Vec<Viewport> {
+        self.scene_view.panes()
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.scene_view.is_modified()
+    }
+
+    pub fn status(&self) -> &StatusBar {
+        self.scene_view.status()
+    }
+
+    pub fn record(&mut self, path: &str) {
+        match Recorder::create(path) {
+            Ok(recorder) => self.recorder = Some(recorder),
+            Err(err) => println!("Cannot record to {}: {}", path, err),
+        }
+    }
+
+    // Live inputs are ignored until the replay is over
+    pub fn replay(&mut self, path: &str) {
+        match Replay::load(path) {
+            Ok(replay) => self.input = Box::new(replay),
+            Err(err) => println!("Cannot replay {}: {}", path, err),
         }
     }
 
+    // Runs the next frame of the input source, if any
+    pub fn step(&mut self) -> bool {
+        let frame = match self.input.next() {
+            Some(frame) => frame,
+            None => return false,
+        };
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(&frame);
+        }
+        // Pasting from outside the editor would not be replayed
+        let system = self.input.is_live() && self.recorder.is_none();
+        self.scene_view.set_system_clipboard(system);
+        self.update_frame(&frame);
+
+        true
+    }
+
+    // Everything but drawing only depends on the frame, so that replays are
+    // deterministic
+    fn update_frame(&mut self, frame: &Frame) {
+        self.scene_view.set_cell(self.catalogue.cell());
+        self.keyboard.update(frame);
+        self.mouse.update(frame);
+        self.gamepad.update(frame);
+        self.resize(frame.size);
+
+        for &wheel in &frame.wheel {
+            self.wheel(wheel);
+        }
+
+        if self.commands.is_some() {
+            self.commands();
+        } else if self.menu.is_some() {
+            self.menu();
+        } else {
+            self.events();
+            self.gamepad();
+            self.view();
+        }
+
+        // Frame times rather than the clock keep replays deterministic
+        let delta = frame.time.checked_sub(self.time).unwrap_or_default();
+        self.scene_view.viewport.animate(delta);
+        self.scene_view.update_status(delta);
+        self.time = frame.time;
+    }
+
     fn events(&mut self) {
-        if self.keyboard.is_pressed(KeyCode::Tab) {
-            self.scene_view.selection.clear();
+        if self.scene_view.is_typing() {
+            return;
+        }
+
+        if self.keyboard.action(Action::SwitchView) {
+            self.scene_view.selection.cancel();
             self.view.switch();
         }
 
-        if self.keyboard.is_pressed(KeyCode::B) {
+        if self.keyboard.action(Action::Help) {
+            self.help = !self.help;
+        }
+
+        if self.keyboard.action(Action::Commands) {
+            self.commands = Some(CommandPalette::new());
+        }
+
+        // In the palette, typed characters go to the filter
+        if self.in_palette() {
+            return;
+        }
+
+        if self.keyboard.action(Action::TogglePanel) {
+            self.panel = !self.panel;
+        }
+
+        if self.keyboard.action(Action::ZoomFit) {
+            self.scene_view.fit(self.scene_area(), false);
+        } else if self.keyboard.action(Action::ZoomSelection) {
+            self.scene_view.fit(self.scene_area(), true);
+        }
+
+        if self.keyboard.action(Action::RandomBackground) {
             if self.background == Color::BLACK {
                 let mut rng = thread_rng();
                 self.background = Color::from_rgb(rng.gen(), rng.gen(), rng.gen());
@@ -57,6 +205,162 @@ impl Editor {
         }
     }
 
+    // The chosen command runs on the next update
+    fn commands(&mut self) {
+        if let Some(commands) = &mut self.commands {
+            commands.events(&self.keyboard);
+
+            if self.keyboard.is_pressed(KeyCode::Return) {
+                if let Some(action) = commands.selected() {
+                    self.keyboard.trigger(action);
+                }
+                self.commands = None;
+            } else if self.keyboard.is_pressed(KeyCode::Escape) {
+                self.commands = None;
+            }
+        }
+    }
+
+    // The click choosing an item goes no further
+    fn menu(&mut self) {
+        if let Some(menu) = &self.menu {
+            if let Some(click) = self.mouse.left_click() {
+                if let Some(item) = menu.item_at(click) {
+                    self.scene_view.menu_item(item, menu.cell());
+                }
+                self.menu = None;
+            } else if self.mouse.right_click().is_some()
+                || self.keyboard.is_pressed(KeyCode::Escape)
+            {
+                self.menu = None;
+            }
+        }
+    }
+
+    // The palette gets the inputs in the tiles view, or when hovering the panel
+    // of the scene view while the scene is neither being selected nor named
+    fn in_palette(&self) -> bool {
+        match self.view {
+            Views::Scene =>
+                self.panel
+                    && self.tiles_view.contains(self.mouse.position())
+                    && self.scene_view.selection.selection().is_none()
+                    && !self.scene_view.is_typing(),
+            Views::Tiles => true,
+        }
+    }
+
+    // The part of the focused pane showing the scene, left of the panel and
+    // above the status bar
+    fn scene_area(&self) -> Rect {
+        let screen = self.scene_view.viewport.screen();
+        let panel = if self.panel { PANEL_WIDTH } else { 0. };
+        let right = screen.right().min(self.size.x - panel);
+
+        [
+            screen.x,
+            screen.y,
+            right - screen.x,
+            screen.h - StatusBar::HEIGHT,
+        ]
+        .into()
+    }
+
+    fn resize(&mut self, size: Point) {
+        let Point { x: w, y: h } = size;
+        let panel = if self.panel { PANEL_WIDTH } else { 0. };
+
+        self.size = size;
+        self.scene_view.resize(size, w - panel);
+        self.tiles_view.set_rect(match self.view {
+            Views::Scene => [w - PANEL_WIDTH, 0., PANEL_WIDTH, h - StatusBar::HEIGHT].into(),
+            Views::Tiles => [0., 0., w, h].into(),
+        });
+    }
+
+    fn draw_panel(&mut self, ctx: &mut Context, tile_renderer: &mut TileRenderer) {
+        let (w, h) = drawable_size(ctx);
+
+        MeshBuilder::new()
+            .rectangle(
+                DrawMode::fill(),
+                [w - PANEL_WIDTH, 0., PANEL_WIDTH, h].into(),
+                Color::new(0.1, 0.1, 0.1, 1.),
+            )
+            .unwrap()
+            .build(ctx)
+            .unwrap()
+            .draw(ctx, DrawParam::new())
+            .unwrap();
+
+        tile_renderer.clear();
+        self.tiles_view.draw(
+            ctx,
+            tile_renderer,
+            &self.mouse,
+            &self.scene_view.prefabs,
+            self.scene_view.pencil.as_ref(),
+        );
+    }
+
+    fn view(&mut self) {
+        if self.view == Views::Scene {
+            self.scene_view.focus(&self.mouse);
+        }
+
+        if self.in_palette() {
+            self.tiles_view.events(
+                &self.keyboard,
+                &self.mouse,
+                &self.scene_view.prefabs,
+                &self.catalogue,
+            );
+            self.tiles_view
+                .update(&mut self.scene_view.pencil, &self.scene_view.prefabs);
+        } else if let Some(position) = self
+            .mouse
+            .right_click()
+            .filter(|_| self.view == Views::Scene && !self.scene_view.is_typing())
+        {
+            let menu = self.scene_view.context_menu(position);
+            self.menu = Some(menu).filter(|menu| !menu.is_empty());
+        } else if !self.scene_view.minimap(&self.mouse) {
+            self.scene_view.events(&self.keyboard, &self.mouse);
+            self.scene_view.update(&self.keyboard, &self.mouse);
+        }
+
+        if self.view == Views::Scene {
+            self.scene_view.gamepad(&self.gamepad);
+        }
+    }
+
+    // Shoulder buttons cycle through the palette
+    fn gamepad(&mut self) {
+        let delta = if self.gamepad.is_pressed(Button::RightTrigger) {
+            1
+        } else if self.gamepad.is_pressed(Button::LeftTrigger) {
+            -1
+        } else {
+            return;
+        };
+
+        if let Some(pencil) = self.tiles_view.cycle(delta, &self.scene_view.prefabs) {
+            self.scene_view.pencil = Some(pencil);
+        }
+    }
+
+    fn wheel(&mut self, Point { x, y }: Point) {
+        if self.in_palette() {
+            self.tiles_view.wheel(y, self.keyboard.ctrl());
+        } else if self.keyboard.ctrl() {
+            self.scene_view.viewport.zoom(self.mouse.position(), y);
+        } else {
+            self.scene_view
+                .viewport
+                .scroll([x * SCROLL_SENSITIVITY.x, y * SCROLL_SENSITIVITY.y]);
+        }
+    }
+
     fn elapsed(&self) -> Duration {
         self.now.elapsed()
     }
@@ -79,55 +383,100 @@ impl Editor {
 }
 
 impl EventHandler for Editor {
-    fn mouse_wheel_event(&mut self, ctx: &mut Context, x: f32, y: f32) {
-        if self.view == Views::Scene {
-            if self.keyboard.ctrl() {
-                self.scene_view.viewport.zoom(self.mouse.position(), y);
-            } else {
-                self.scene_view
-                    .viewport
-                    .translate([x * SCROLL_SENSITIVITY.x, y * SCROLL_SENSITIVITY.y]);
-            }
+    // Escape cancels the pending editor action, if any, instead of quitting
+    fn key_down_event(&mut self, ctx: &mut Context, key: KeyCode, _: KeyMods, _: bool) {
+        let pending = self.menu.is_some()
+            || self.commands.is_some()
+            || self.scene_view.is_pasting()
+            || self.scene_view.is_typing()
+            || (self.in_palette() && self.tiles_view.is_filtering());
+
+        if key == KeyCode::Escape && !pending {
+            event::quit(ctx);
         }
     }
 
+    fn text_input_event(&mut self, _: &mut Context, character: char) {
+        self.input.text_input(character);
+    }
+
+    // Applied on the next update
+    fn mouse_wheel_event(&mut self, _: &mut Context, x: f32, y: f32) {
+        self.input.wheel([x, y].into());
+    }
+
+    fn gamepad_button_down_event(&mut self, _: &mut Context, button: Button, _: GamepadId) {
+        self.input.gamepad(GamepadEvent::Down(button));
+    }
+
+    fn gamepad_button_up_event(&mut self, _: &mut Context, button: Button, _: GamepadId) {
+        self.input.gamepad(GamepadEvent::Up(button));
+    }
+
+    fn gamepad_axis_event(&mut self, _: &mut Context, axis: Axis, value: f32, _: GamepadId) {
+        self.input.gamepad(GamepadEvent::Axis(axis, value));
+    }
+
     fn update(&mut self, ctx: &mut Context) -> GameResult<()> {
         let now = Instant::now();
-        // let delta = now - self.now;
 
-        self.keyboard.update(ctx);
-        self.mouse.update(ctx);
-        self.events();
-
-        match self.view {
-            Views::Scene => {
-                self.scene_view.events(ctx, &self.keyboard, &self.mouse);
-                self.scene_view.update(ctx, &self.keyboard, &self.mouse);
-            }
-            Views::Tiles => {
-                self.tiles_view.events(ctx, &self.keyboard, &self.mouse);
-                self.tiles_view.update(ctx, &mut self.scene_view.pencil);
-            }
+        self.input.poll(ctx, self.start.elapsed());
+        if !self.step() {
+            println!("Replay finished");
+            self.input = Box::new(LiveInput::new());
         }
+        self.scene_view.reload();
 
         self.now = now;
         Ok(())
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
+        let mut tile_renderer = self
+            .tile_renderer
+            .take()
+            .unwrap_or_else(|| TileRenderer::new(ctx));
+        if let Some(message) = tile_renderer.reload(ctx) {
+            self.scene_view.message(message);
+        }
         clear(ctx, self.background);
-        self.tile_renderer.clear();
+        tile_renderer.clear();
 
         match self.view {
             Views::Scene => {
-                self.scene_view
-                    .draw(ctx, &mut self.tile_renderer, &self.mouse);
+                self.scene_view.draw(ctx, &mut tile_renderer, &self.mouse);
+
+                if self.panel {
+                    self.draw_panel(ctx, &mut tile_renderer);
+                }
+
+                self.scene_view.draw_status(ctx, &self.mouse);
+
+                if let Some(menu) = &self.menu {
+                    menu.draw(ctx, &self.mouse);
+                }
             }
             Views::Tiles => {
-                self.tiles_view.draw(ctx, &mut self.tile_renderer);
+                self.tiles_view.draw(
+                    ctx,
+                    &mut tile_renderer,
+                    &self.mouse,
+                    &self.scene_view.prefabs,
+                    self.scene_view.pencil.as_ref(),
+                );
             }
         }
 
+        if self.help {
+            self.keyboard.keymap().draw(ctx);
+        }
+
+        if let Some(commands) = &self.commands {
+            commands.draw(ctx, self.keyboard.keymap());
+        }
+
+        self.catalogue = tile_renderer.catalogue().clone();
+        self.tile_renderer = Some(tile_renderer);
         present(ctx)?;
         self.sleep();
         Ok(())
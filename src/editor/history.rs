@@ -2,8 +2,9 @@ use super::*;
 
 #[derive(Clone, Default, Debug)]
 pub struct History<T> {
-    history: Vec<T>, // From newest to oldest
-    current: usize,  // < MAX - 1
+    history:  Vec<(usize, T)>, // From newest to oldest, with their revisions
+    current:  usize,           // < MAX - 1
+    revision: usize,           // Of the newest
 }
 
 impl<T: Clone> History<T> {
@@ -11,11 +12,12 @@ impl<T: Clone> History<T> {
 
     pub fn new(t: T) -> Self {
         let mut history = Vec::with_capacity(Self::MAX);
-        history.push(t);
+        history.push((0, t));
 
         Self {
             history,
             current: 0,
+            revision: 0,
         }
     }
 
@@ -28,7 +30,12 @@ impl<T: Clone> History<T> {
             Self::MAX
         );
 
-        self.history.get(self.current).unwrap()
+        &self.history.get(self.current).unwrap().1
+    }
+
+    // Identifies the current state, e.g. to know whether it changed
+    pub fn revision(&self) -> usize {
+        self.history.get(self.current).unwrap().0
     }
 
     pub fn edit<U>(&mut self, f: impl FnOnce(&mut T) -> U) -> U {
@@ -38,6 +45,20 @@ impl<T: Clone> History<T> {
         ret
     }
 
+    // Replaces the current state, e.g. while it is still being made, as a new
+    // revision but without a new undo step
+    pub fn amend(&mut self, t: T) {
+        self.revision += 1;
+        self.history[self.current] = (self.revision, t);
+    }
+
+    // Forgets the previous states
+    pub fn reset(&mut self, t: T) {
+        self.revision += 1;
+        self.history = vec![(self.revision, t)];
+        self.current = 0;
+    }
+
     pub fn undo(&mut self) -> bool {
         if self.current + 1 < self.history.len().min(Self::MAX) {
             self.current += 1;
@@ -57,16 +78,10 @@ impl<T: Clone> History<T> {
     }
 
     pub fn events(&mut self, keyboard: &Keyboard) {
-        let ctrl = keyboard.ctrl();
-        let shift = keyboard.shift();
-        let z = keyboard.is_pressed(KeyCode::Z);
-
-        if ctrl && z {
-            if shift {
-                self.redo();
-            } else {
-                self.undo();
-            }
+        if keyboard.action(Action::Undo) {
+            self.undo();
+        } else if keyboard.action(Action::Redo) {
+            self.redo();
         }
     }
 
@@ -75,7 +90,8 @@ impl<T: Clone> History<T> {
         self.history.splice(0..self.current, []);
         self.current = 0;
         // Insert new
-        self.history.insert(0, t);
+        self.revision += 1;
+        self.history.insert(0, (self.revision, t));
         // Remove olds
         self.history.truncate(Self::MAX);
     }
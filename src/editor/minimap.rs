@@ -0,0 +1,175 @@
+use super::*;
+
+// The whole scene at one pixel per cell, enlarged when it is small and
+// shrunk when it is large, in the top left corner of each viewport. Hidden
+// until toggled, not to cover the origin of the scene
+#[derive(Clone, Debug)]
+pub struct Minimap {
+    shown: bool,
+    image: Option<(usize, Option<Image>)>,
+}
+
+impl Minimap {
+    const MARGIN: f32 = 10.;
+    const SIZE: f32 = 160.;
+    const ZOOM: f32 = 4.;
+
+    pub fn new() -> Self {
+        Self {
+            shown: false,
+            image: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    // The cells shown, and where
+    fn layout(scene: &Scene, viewport: Viewport) -> Option<((Range<i16>, Range<i16>), Rect)> {
+        let (x, y) = scene.cells(Show::new()).bounds()?;
+        let (w, h) = (x.len() as f32, y.len() as f32);
+        let zoom = (Self::SIZE / w).min(Self::SIZE / h).min(Self::ZOOM);
+
+        let screen = viewport.screen();
+
+        Some((
+            (x, y),
+            [
+                screen.x + Self::MARGIN,
+                screen.y + Self::MARGIN,
+                w * zoom,
+                h * zoom,
+            ]
+            .into(),
+        ))
+    }
+
+    // Pressing the minimap centers the viewport on the cell under the mouse
+    pub fn events(&self, mouse: &Mouse, scene: &Scene, viewport: &mut Viewport) -> bool {
+        let start = mouse
+            .left_click()
+            .or_else(|| mouse.left_drag().map(|drag| [drag.x, drag.y].into()));
+        let ((x, y), rect) = match (start, Self::layout(scene, *viewport)) {
+            (Some(start), Some((bounds, rect))) if self.shown && rect.contains(start) =>
+                (bounds, rect),
+            _ => return false,
+        };
+
+        let zoom = rect.w / x.len() as f32;
+        let position = mouse.position();
+        let cell = viewport.cell();
+        let center = Point {
+            x: (x.start as f32 + (position.x - rect.x) / zoom) * cell.x,
+            y: (y.start as f32 + (position.y - rect.y) / zoom) * cell.y,
+        };
+        viewport.center_on(center);
+
+        true
+    }
+
+    // Walls over floors, cells sharing pixels when there are more of them
+    // than the minimap is wide or high. Returns the width, the height and the
+    // pixels
+    pub fn pixels(scene: &Scene, (x, y): &(Range<i16>, Range<i16>)) -> (usize, usize, Vec<u8>) {
+        let (w, h) = (x.len(), y.len());
+        let (image_w, image_h) = (w.min(Self::SIZE as usize), h.min(Self::SIZE as usize));
+        let mut rgba = vec![0; image_w * image_h * 4];
+        let mut paint = |cell: &Point<i16>, color: [u8; 4]| {
+            let column = (cell.x as i32 - x.start as i32) as usize * image_w / w;
+            let row = (cell.y as i32 - y.start as i32) as usize * image_h / h;
+            let i = (row * image_w + column) * 4;
+            rgba[i..i + 4].copy_from_slice(&color);
+        };
+
+        for cell in scene.floors.keys() {
+            paint(cell, [90, 90, 110, 255]);
+        }
+        for (cell, walls) in &scene.walls {
+            if walls.bottom.is_some() {
+                paint(cell, [170, 140, 100, 255]);
+            } else if *walls != Walls::default() {
+                paint(cell, [120, 100, 80, 255]);
+            }
+        }
+
+        (image_w, image_h, rgba)
+    }
+
+    fn image(ctx: &mut Context, scene: &Scene, bounds: &(Range<i16>, Range<i16>)) -> Option<Image> {
+        let (w, h, rgba) = Self::pixels(scene, bounds);
+
+        match Image::from_rgba8(ctx, w as u16, h as u16, &rgba) {
+            Ok(mut image) => {
+                image.set_filter(FilterMode::Nearest);
+                Some(image)
+            }
+            Err(err) => {
+                println!("Cannot draw the minimap: {}", err);
+                None
+            }
+        }
+    }
+
+    // The image is only rebuilt when the scene changes
+    pub fn draw(&mut self, ctx: &mut Context, scene: &Scene, revision: usize, viewport: Viewport) {
+        let (bounds, rect) = match Self::layout(scene, viewport).filter(|_| self.shown) {
+            Some(layout) => layout,
+            None => return,
+        };
+        let zoom = rect.w / bounds.0.len() as f32;
+
+        if self.image.as_ref().map(|(cached, _)| *cached) != Some(revision) {
+            self.image = Some((revision, Self::image(ctx, scene, &bounds)));
+        }
+
+        let border = Rect::new(rect.x - 2., rect.y - 2., rect.w + 4., rect.h + 4.);
+        MeshBuilder::new()
+            .rectangle(DrawMode::fill(), border, Color::new(0., 0., 0., 0.7))
+            .unwrap()
+            .build(ctx)
+            .unwrap()
+            .draw(ctx, DrawParam::new())
+            .unwrap();
+
+        if let Some((_, Some(image))) = &self.image {
+            let scale = [
+                rect.w / image.width() as f32,
+                rect.h / image.height() as f32,
+            ];
+            image
+                .draw(ctx, DrawParam::new().dest([rect.x, rect.y]).scale(scale))
+                .unwrap();
+        }
+
+        // The viewport, clipped to the minimap
+        let screen = viewport.screen();
+        let start = viewport.coordinates([screen.x, screen.y]);
+        let end = viewport.coordinates([screen.right(), screen.bottom()]);
+        let map = |x: f32, start: i16, min: f32, max: f32| {
+            (min + (x - start as f32) * zoom).max(min).min(max)
+        };
+        let (left, top) = (
+            map(start.x, bounds.0.start, rect.x, rect.x + rect.w),
+            map(start.y, bounds.1.start, rect.y, rect.y + rect.h),
+        );
+        let (right, bottom) = (
+            map(end.x, bounds.0.start, rect.x, rect.x + rect.w),
+            map(end.y, bounds.1.start, rect.y, rect.y + rect.h),
+        );
+
+        if right > left && bottom > top {
+            MeshBuilder::new()
+                .rectangle(
+                    DrawMode::stroke(1.),
+                    [left, top, right - left, bottom - top].into(),
+                    Color::WHITE,
+                )
+                .unwrap()
+                .build(ctx)
+                .unwrap()
+                .draw(ctx, DrawParam::new())
+                .unwrap();
+        }
+    }
+}
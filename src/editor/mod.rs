@@ -1,30 +1,60 @@
 use super::*;
 
+mod actions;
+mod clipboard;
+mod command_palette;
+mod context_menu;
 mod editor;
 mod history;
+mod minimap;
 mod pencil;
+mod prefab;
+mod replace;
 mod scene;
 mod scene_view;
+mod status_bar;
 mod tiles_view;
+mod transform;
 mod viewport;
+mod watch;
 
+pub use actions::*;
+pub use clipboard::*;
+pub use command_palette::*;
+pub use context_menu::*;
 pub use editor::*;
 pub use history::*;
+pub use minimap::*;
 pub use pencil::*;
+pub use prefab::*;
+pub use replace::*;
 pub use scene::*;
 pub use scene_view::*;
+pub use status_bar::*;
 pub use tiles_view::*;
+pub use transform::*;
 pub use viewport::*;
+pub use watch::*;
 
+// The tiles of all the elements are listed in `TILE_NAMES`, each enum's
+// being found from its `OFFSET` there
 macro_rules! elements {
-    ($($Name:ident $N:literal [$($Variant:ident $Tile:ident)*])*) => { $(
+    ($($Name:ident [$($Variant:ident $Tile:ident)*])*) => {
+        pub const TILE_NAMES: &[&str] = &[$($(stringify!($Tile),)*)*];
+
+        elements!(@offset 0; $($Name [$($Variant $Tile)*])*);
+    };
+    (@offset $offset:expr;) => {};
+    (@offset $offset:expr; $Name:ident [$($Variant:ident $Tile:ident)*] $($rest:tt)*) => {
         #[derive(Copy, Clone, PartialEq, Debug)]
         pub enum $Name { $($Variant,)* }
         pub use $Name::*;
 
         impl $Name {
+            const OFFSET: usize = $offset;
+
             pub fn random() -> Self {
-                Self::from_usize(thread_rng().gen_range(0..$N))
+                Self::from_usize(thread_rng().gen_range(0..Self::all().len()))
             }
 
             pub fn from_usize(u: usize) -> Self {
@@ -33,21 +63,33 @@ macro_rules! elements {
                 *unsafe { all.get_unchecked(u % all.len()) }
             }
 
-            pub const fn all() -> [Self; $N] {
-                [$(Self::$Variant,)*]
+            pub const fn all() -> &'static [Self] {
+                &[$(Self::$Variant,)*]
             }
 
-            pub fn tile(&self) -> Tile {
+            pub fn name(&self) -> &'static str {
                 match self {
-                    $(Self::$Variant => Tile::$Tile,)*
+                    $(Self::$Variant => stringify!($Variant),)*
                 }
             }
+
+            pub fn from_name(name: &str) -> Option<Self> {
+                Self::all().iter().copied().find(|element| element.name() == name)
+            }
         }
-    )* };
+
+        impl Sprite for $Name {
+            fn index(&self) -> usize {
+                Self::OFFSET + *self as usize
+            }
+        }
+
+        elements!(@offset $offset + $Name::all().len(); $($rest)*);
+    };
 }
 
 elements!(
-    FloorEnum 12 [
+    FloorEnum [
         Floor   FLOOR_1
         Cracks1 FLOOR_2
         Cracks2 FLOOR_3
@@ -61,7 +103,7 @@ elements!(
         Hole    HOLE
         Edge    EDGE
     ]
-    WallEnum 10 [
+    WallEnum [
         Wall            WALL_MID
         // Column          WALL_COLUMN_MID
         SmallHole       WALL_HOLE_1
@@ -74,9 +116,8 @@ elements!(
         WaterFountain   WALL_FOUNTAIN_MID_BLUE_ANIM_0
         Goo             WALL_GOO
     ]
-    /*
-    Creature 22 [
-        // Hero
+    CreatureEnum [
+        // Heroes
         MaleElf         ELF_M_IDLE_ANIM_0
         FemaleElf       ELF_F_IDLE_ANIM_0
         MaleKnight      KNIGHT_M_IDLE_ANIM_0
@@ -103,13 +144,58 @@ elements!(
         // Misc
         Muddy           MUDDY_IDLE_ANIM_0
         Swampy          SWAMPY_IDLE_ANIM_0
+        // Bosses
+        BigDemon        BIG_DEMON_IDLE_ANIM_0
+        BigZombie       BIG_ZOMBIE_IDLE_ANIM_0
+        Ogre            OGRE_IDLE_ANIM_0
     ]
-    Boss 3 [
-        BigDemon        BIG_DEMON_IDLE_ANIM_0  // Demon
-        BigZombie       BIG_ZOMBIE_IDLE_ANIM_0 // Undead
-        Ogre            OGRE_IDLE_ANIM_0       // Orc
+    ItemEnum [
+        // Weapons
+        Knife           WEAPON_KNIFE
+        RustySword      WEAPON_RUSTY_SWORD
+        RegularSword    WEAPON_REGULAR_SWORD
+        RedGemSword     WEAPON_RED_GEM_SWORD
+        BigHammer       WEAPON_BIG_HAMMER
+        Hammer          WEAPON_HAMMER
+        SpikedBaton     WEAPON_BATON_WITH_SPIKES
+        Mace            WEAPON_MACE
+        Katana          WEAPON_KATANA
+        SawSword        WEAPON_SAW_SWORD
+        AnimeSword      WEAPON_ANIME_SWORD
+        Axe             WEAPON_AXE
+        Machete         WEAPON_MACHETE
+        Cleaver         WEAPON_CLEAVER
+        DuelSword       WEAPON_DUEL_SWORD
+        KnightSword     WEAPON_KNIGHT_SWORD
+        GoldenSword     WEAPON_GOLDEN_SWORD
+        LavishSword     WEAPON_LAVISH_SWORD
+        RedMagicStaff   WEAPON_RED_MAGIC_STAFF
+        GreenMagicStaff WEAPON_GREEN_MAGIC_STAFF
+        Spear           WEAPON_SPEAR
+        Arrow           WEAPON_ARROW
+        Bow             WEAPON_BOW
+        // Flasks
+        BigRedFlask     FLASK_BIG_RED
+        BigBlueFlask    FLASK_BIG_BLUE
+        BigGreenFlask   FLASK_BIG_GREEN
+        BigYellowFlask  FLASK_BIG_YELLOW
+        RedFlask        FLASK_RED
+        BlueFlask       FLASK_BLUE
+        GreenFlask      FLASK_GREEN
+        YellowFlask     FLASK_YELLOW
+        // Misc
+        Coin            COIN_ANIM_0
+        EmptyChest      CHEST_EMPTY_OPEN_ANIM_0
+        FullChest       CHEST_FULL_OPEN_ANIM_0
+        MimicChest      CHEST_MIMIC_OPEN_ANIM_0
+        Crate           CRATE
+        Skull           SKULL
+    ]
+    // Sides of cells, drawn with the opposite side of a wall
+    WallSideEnum [
+        LeftSide        WALL_SIDE_MID_RIGHT
+        RightSide       WALL_SIDE_MID_LEFT
     ]
-    */
 );
 
 impl Default for WallEnum {
@@ -122,13 +208,42 @@ impl Default for WallEnum {
 pub enum Element {
     Floor(FloorEnum),
     Wall(WallEnum),
+    Creature(CreatureEnum),
+    Item(ItemEnum),
+}
+
+impl Sprite for Element {
+    fn index(&self) -> usize {
+        match self {
+            Self::Floor(floor) => floor.index(),
+            Self::Wall(wall) => wall.index(),
+            Self::Creature(creature) => creature.index(),
+            Self::Item(item) => item.index(),
+        }
+    }
 }
 
 impl Element {
-    pub fn tile(&self) -> Tile {
+    // Every element, by category
+    pub fn categories() -> [(&'static str, Vec<Self>); 4] {
+        fn all<T: Copy>(all: &[T], element: fn(T) -> Element) -> Vec<Element> {
+            all.iter().copied().map(element).collect()
+        }
+
+        [
+            ("Floors", all(FloorEnum::all(), Self::Floor)),
+            ("Walls", all(WallEnum::all(), Self::Wall)),
+            ("Creatures", all(CreatureEnum::all(), Self::Creature)),
+            ("Items", all(ItemEnum::all(), Self::Item)),
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
         match self {
-            Self::Floor(floor) => floor.tile(),
-            Self::Wall(wall) => wall.tile(),
+            Self::Floor(floor) => floor.name(),
+            Self::Wall(wall) => wall.name(),
+            Self::Creature(creature) => creature.name(),
+            Self::Item(item) => item.name(),
         }
     }
 }
@@ -160,6 +275,38 @@ impl Orientation {
             West => South,
         };
     }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            North => "North",
+            East => "East",
+            South => "South",
+            West => "West",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [North, East, South, West]
+            .iter()
+            .copied()
+            .find(|orientation| orientation.name() == name)
+    }
+
+    pub fn mirror_horizontal(&mut self) {
+        *self = match *self {
+            East => West,
+            West => East,
+            orientation => orientation,
+        };
+    }
+
+    pub fn mirror_vertical(&mut self) {
+        *self = match *self {
+            North => South,
+            South => North,
+            orientation => orientation,
+        };
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
@@ -199,7 +346,7 @@ impl Walls {
         }
     }
 
-    pub fn tile(grid: [[Self; 3]; 3]) -> [Option<Tile>; 3] {
+    pub fn tile(grid: [[Self; 3]; 3], catalogue: &Catalogue) -> [Option<Tile>; 3] {
         let [[tl, t, tr], [ml, m, mr], [bl, b, br]] = grid;
 
         macro_rules! g {
@@ -217,17 +364,17 @@ impl Walls {
 
         [
             if let Some(wall) = m.bottom {
-                Some(wall.tile())
+                Some(catalogue.tile(wall))
             } else {
                 None
             },
             if m.left {
-                Some(Tile::WALL_SIDE_MID_RIGHT)
+                Some(catalogue.tile(LeftSide))
             } else {
                 None
             },
             if m.right {
-                Some(Tile::WALL_SIDE_MID_LEFT)
+                Some(catalogue.tile(RightSide))
             } else {
                 None
             },
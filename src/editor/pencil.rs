@@ -1,26 +1,33 @@
 use super::*;
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+// Prefabs are named, their order in the library changing as they are saved
+#[derive(Clone, PartialEq, Debug)]
 pub enum Pencil {
     Floor((FloorEnum, Orientation)),
     Wall(WallEnum),
+    Prefab(String),
 }
 
 impl Pencil {
     pub fn events(&mut self, keyboard: &Keyboard) {
-        let shift = keyboard.shift();
-
-        if keyboard.is_pressed(KeyCode::R) {
-            if let Self::Floor((_, orientation)) = self {
-                if shift {
-                    orientation.rotate_left();
-                } else {
-                    orientation.rotate_right();
-                }
+        if let Self::Floor((_, orientation)) = self {
+            if keyboard.action(Action::RotateRight) {
+                orientation.rotate_right();
+            } else if keyboard.action(Action::RotateLeft) {
+                orientation.rotate_left();
             }
         }
     }
 
+    pub fn name(&self) -> String {
+        match self {
+            Pencil::Floor((floor, orientation)) =>
+                format!("{} {}", floor.name(), orientation.name()),
+            Pencil::Wall(wall) => wall.name().to_string(),
+            Pencil::Prefab(name) => name.clone(),
+        }
+    }
+
     pub fn draw(&self, tile_renderer: &mut TileRenderer, position: Point, viewport: &Viewport) {
         let magnet = viewport.magnetize(position);
         let position = viewport.coordinates(position);
@@ -28,16 +35,20 @@ impl Pencil {
 
         match *self {
             Pencil::Floor((floor, orientation)) =>
-                tile_renderer.add((floor.tile(), magnet, orientation)),
-            Pencil::Wall(wall) => tile_renderer.add((
-                thirds((
+                tile_renderer.add((tile_renderer.tile(floor), magnet, orientation)),
+            Pencil::Wall(wall) => {
+                let catalogue = tile_renderer.catalogue();
+                let tile = thirds((
                     x,
-                    || Tile::WALL_SIDE_MID_RIGHT,
-                    || wall.tile(),
-                    || Tile::WALL_SIDE_MID_LEFT,
-                )),
-                magnet,
-            )),
+                    || catalogue.tile(LeftSide),
+                    || catalogue.tile(wall),
+                    || catalogue.tile(RightSide),
+                ));
+
+                tile_renderer.add((tile, magnet))
+            }
+            // Drawn by the scene view, which owns the prefabs
+            Pencil::Prefab(_) => {}
         }
     }
 }
@@ -0,0 +1,115 @@
+use super::*;
+
+// The cells are those of the prefab when it was stamped, not saved with the
+// scene but found again in the library when it is opened
+#[derive(Clone, PartialEq, Debug)]
+pub struct Instance {
+    pub prefab: String,
+    pub delta:  Point<i16>,
+    pub cells:  Cells,
+}
+
+// Prefabs are normalized so that their bounds start at the origin
+#[derive(Clone, Debug)]
+pub struct Prefab {
+    pub name:  String,
+    pub cells: Cells,
+    pub scene: Scene,
+}
+
+impl Prefab {
+    // Names are file names in the prefab directory, so anything but letters,
+    // digits, dashes and underscores is dropped
+    pub fn new(name: &str, cells: &Cells, scene: Scene) -> Option<Self> {
+        let name = name
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("_")
+            .chars()
+            .filter(|&c| c.is_alphanumeric() || c == '_' || c == '-')
+            .collect::<String>();
+        let (x, y) = cells.bounds()?;
+        let delta = [-x.start, -y.start];
+        let mut normalized = Scene::new();
+        normalized.paste(scene, delta);
+
+        if name.is_empty() {
+            None
+        } else {
+            Some(Self {
+                name,
+                cells: cells.translate(delta),
+                scene: normalized,
+            })
+        }
+    }
+
+    pub fn size(&self) -> Point<i16> {
+        let (x, y) = self.cells.bounds().unwrap_or_default();
+
+        [x.end, y.end].into()
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct Prefabs {
+    prefabs: Vec<Prefab>,
+}
+
+impl Prefabs {
+    const EXTENSION: &'static str = "prefab";
+
+    pub fn load() -> Self {
+        let mut prefabs = std::fs::read_dir(PREFABS)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != Self::EXTENSION {
+                    return None;
+                }
+
+                let name = path.file_stem()?.to_str()?.to_string();
+                let text = std::fs::read_to_string(&path).ok()?;
+                let (cells, scene) = Clipboard::from_text(&text)?;
+
+                Prefab::new(&name, &cells, scene)
+            })
+            .collect::<Vec<_>>();
+        prefabs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self { prefabs }
+    }
+
+    pub fn get(&self, i: usize) -> Option<&Prefab> {
+        self.prefabs.get(i)
+    }
+
+    pub fn len(&self) -> usize {
+        self.prefabs.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Prefab> {
+        self.prefabs.iter()
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Prefab> {
+        self.prefabs.iter().find(|prefab| prefab.name == name)
+    }
+
+    // The library only changes once the file is written
+    pub fn save(&mut self, prefab: Prefab) -> std::io::Result<()> {
+        let path = Path::new(PREFABS).join(format!("{}.{}", prefab.name, Self::EXTENSION));
+        let text = Clipboard::to_text(&prefab.cells, &prefab.scene);
+
+        std::fs::create_dir_all(PREFABS).and_then(|()| std::fs::write(&path, text))?;
+
+        match self.prefabs.iter_mut().find(|old| old.name == prefab.name) {
+            Some(old) => *old = prefab,
+            None => self.prefabs.push(prefab),
+        }
+
+        Ok(())
+    }
+}
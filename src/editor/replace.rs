@@ -0,0 +1,45 @@
+use super::*;
+
+type ReplaceFloor = (FloorEnum, Option<Orientation>);
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Replace {
+    Floor((ReplaceFloor, ReplaceFloor)),
+    Wall((WallEnum, WallEnum)),
+}
+
+impl Replace {
+    // Replaces the element found at `position` with the pencil's one, with
+    // orientations only when `orientation` is set
+    pub fn new(
+        scene: &Scene,
+        position: Point<i16>,
+        pencil: &Pencil,
+        orientation: bool,
+        show: Show,
+    ) -> Option<Self> {
+        let orientation = |o| if orientation { Some(o) } else { None };
+
+        match *pencil {
+            Pencil::Floor((to, to_orientation)) => {
+                let &(from, from_orientation) =
+                    scene.floors.get(&position).filter(|_| show.floors())?;
+
+                Some(Self::Floor((
+                    (from, orientation(from_orientation)),
+                    (to, orientation(to_orientation)),
+                )))
+            }
+            Pencil::Wall(to) => {
+                let from = scene
+                    .walls
+                    .get(&position)
+                    .filter(|_| show.walls())?
+                    .bottom?;
+
+                Some(Self::Wall((from, to)))
+            }
+            Pencil::Prefab(_) => None,
+        }
+    }
+}
@@ -2,25 +2,41 @@ use super::*;
 
 type SceneFloors = HashMap<Point<i16>, (FloorEnum, Orientation)>;
 type SceneWalls = HashMap<Point<i16>, Walls>;
+type SceneBookmarks = HashMap<u8, Bookmark>;
 
 #[derive(Clone, Default, Debug)]
 pub struct Scene {
-    pub floors: SceneFloors,
-    pub walls:  SceneWalls,
+    pub floors:    SceneFloors,
+    pub walls:     SceneWalls,
+    pub instances: Vec<Instance>,
+    pub bookmarks: SceneBookmarks,
+    // The default one when none
+    pub tileset:   Option<String>,
 }
 
 impl Scene {
     pub fn new() -> Self {
         Self {
-            floors: HashMap::default(),
-            walls:  HashMap::default(),
+            floors:    HashMap::default(),
+            walls:     HashMap::default(),
+            instances: Vec::new(),
+            bookmarks: HashMap::default(),
+            tileset:   None,
         }
     }
 
+    pub fn tileset(&self) -> &str {
+        self.tileset.as_deref().unwrap_or(TILESET)
+    }
+
     pub fn render(&self, tile_renderer: &mut TileRenderer, show: Show) {
         if show.floors() {
             for (pos, &(floor, orientation)) in &self.floors {
-                tile_renderer.add((floor.tile(), [pos.x as f32, pos.y as f32], orientation));
+                tile_renderer.add((
+                    tile_renderer.tile(floor),
+                    [pos.x as f32, pos.y as f32],
+                    orientation,
+                ));
             }
         }
 
@@ -51,7 +67,8 @@ impl Scene {
                         .unwrap_or_default(),
                 ];
 
-                for tile in Walls::tile([top_row, middle_row, bottom_row]) {
+                let grid = [top_row, middle_row, bottom_row];
+                for tile in Walls::tile(grid, tile_renderer.catalogue()) {
                     if let Some(tile) = tile {
                         tile_renderer.add((tile, [pos.x as f32, pos.y as f32]));
                     }
@@ -94,36 +111,18 @@ impl Scene {
         }
     }
 
-    pub fn remove_floor(&mut self, (x, y): (Range<i16>, Range<i16>)) {
-        for i in x {
-            for j in y.clone() {
-                self.floors.remove(&([i, j].into()));
-            }
+    pub fn remove_floors(&mut self, cells: &Cells) {
+        for cell in cells.iter() {
+            self.floors.remove(&cell);
         }
     }
 
-    pub fn rotate_floor(
-        &mut self,
-        (x, y): (Range<i16>, Range<i16>),
-        rotate: impl Fn(&mut Orientation),
-    ) {
-        for i in x {
-            for j in y.clone() {
-                self.floors
-                    .get_mut(&([i, j].into()))
-                    .map(|(_, o)| rotate(o));
-            }
-        }
-    }
-
-    pub fn copy_floors(&self, (x, y): (Range<i16>, Range<i16>)) -> SceneFloors {
+    pub fn copy_floors(&self, cells: &Cells) -> SceneFloors {
         let mut copy = SceneFloors::new();
 
-        for i in x {
-            for j in y.clone() {
-                if let Some(&floor) = self.floors.get(&[i, j].into()) {
-                    copy.insert([i, j].into(), floor);
-                }
+        for cell in cells.iter() {
+            if let Some(&floor) = self.floors.get(&cell) {
+                copy.insert(cell, floor);
             }
         }
 
@@ -182,14 +181,18 @@ impl Scene {
         }
     }
 
-    pub fn copy_walls(&self, (x, y): (Range<i16>, Range<i16>)) -> SceneWalls {
+    pub fn remove_walls(&mut self, cells: &Cells) {
+        for cell in cells.iter() {
+            self.walls.remove(&cell);
+        }
+    }
+
+    pub fn copy_walls(&self, cells: &Cells) -> SceneWalls {
         let mut copy = SceneWalls::new();
 
-        for i in x {
-            for j in y.clone() {
-                if let Some(&walls) = self.walls.get(&[i, j].into()) {
-                    copy.insert([i, j].into(), walls);
-                }
+        for cell in cells.iter() {
+            if let Some(&walls) = self.walls.get(&cell) {
+                copy.insert(cell, walls);
             }
         }
 
@@ -204,44 +207,397 @@ impl Scene {
         }
     }
 
-    pub fn remove(&mut self, ranges: (Range<i16>, Range<i16>), show: Show) {
+    // Linked instances losing cells are unlinked
+    pub fn remove(&mut self, cells: &Cells, show: Show) {
+        self.unlink(cells);
+        self.clear(cells, show);
+    }
+
+    // Linked instances with any of `cells`, once edited, no longer follow
+    // their prefab
+    fn unlink(&mut self, cells: &Cells) {
+        self.instances
+            .retain(|instance| !instance.cells.iter().any(|cell| cells.contains(&cell)));
+    }
+
+    fn clear(&mut self, cells: &Cells, show: Show) {
         if show.floors() {
-            self.remove_floor(ranges.clone())
+            self.remove_floors(cells)
         }
 
         if show.walls() {
-            self.bottom_wall(None, ranges.clone());
-            self.left_wall(false, ranges.clone());
-            self.right_wall(false, ranges.clone());
+            self.remove_walls(cells)
         }
     }
 
-    pub fn cut(&mut self, ranges: (Range<i16>, Range<i16>), show: Show) -> Self {
-        let copy = self.copy(ranges.clone(), show);
-        self.remove(ranges, show);
+    pub fn cut(&mut self, cells: &Cells, show: Show) -> Self {
+        let copy = self.copy(cells, show);
+        self.remove(cells, show);
         copy
     }
 
-    pub fn copy(&self, ranges: (Range<i16>, Range<i16>), show: Show) -> Self {
+    pub fn copy(&self, cells: &Cells, show: Show) -> Self {
         let mut copy = Self::new();
 
         if show.floors() {
-            copy.floors = self.copy_floors(ranges.clone());
+            copy.floors = self.copy_floors(cells);
         }
 
         if show.walls() {
-            copy.walls = self.copy_walls(ranges.clone());
+            copy.walls = self.copy_walls(cells);
         }
 
         copy
     }
 
+    pub fn cells(&self, show: Show) -> Cells {
+        let floors = self.floors.keys().filter(|_| show.floors());
+        let walls = self
+            .walls
+            .iter()
+            .filter(|(_, walls)| show.walls() && **walls != Walls::default())
+            .map(|(pos, _)| pos);
+
+        floors.chain(walls).copied().collect()
+    }
+
+    // Flood fills the cells sharing the floor (or, without floor, the walls)
+    // found at `start`
+    pub fn wand(&self, start: Point<i16>, show: Show) -> Cells {
+        let floor = self
+            .floors
+            .get(&start)
+            .filter(|_| show.floors())
+            .map(|&(floor, _)| floor);
+        let walls = self
+            .walls
+            .get(&start)
+            .filter(|walls| show.walls() && **walls != Walls::default())
+            .copied();
+        let same = |pos: &Point<i16>| {
+            if let Some(floor) = floor {
+                self.floors.get(pos).map(|&(floor, _)| floor) == Some(floor)
+            } else if let Some(walls) = walls {
+                self.walls.get(pos) == Some(&walls)
+            } else {
+                false
+            }
+        };
+
+        let mut cells = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            if same(&pos) && cells.insert(pos) {
+                stack.extend([top(pos), bottom(pos), left(pos), right(pos)]);
+            }
+        }
+
+        cells.into_iter().collect()
+    }
+
+    // The cells `wand` selects or, for an empty cell, the empty cells around it
+    // within the bounds of the scene
+    pub fn region(&self, start: Point<i16>, show: Show) -> Cells {
+        let wand = self.wand(start, show);
+        if !wand.is_empty() {
+            return wand;
+        }
+
+        let scene = self.cells(show);
+        let (x, y) = match scene.bounds() {
+            Some((x, y)) if x.contains(&start.x) && y.contains(&start.y) => (x, y),
+            _ => return Cells::from_iter(vec![start]),
+        };
+
+        let mut cells = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            let inside = x.contains(&pos.x) && y.contains(&pos.y);
+            if inside && !scene.contains(&pos) && cells.insert(pos) {
+                stack.extend([top(pos), bottom(pos), left(pos), right(pos)]);
+            }
+        }
+
+        cells.into_iter().collect()
+    }
+
+    // The topmost visible element at `cell`
+    pub fn pick(&self, cell: Point<i16>, show: Show) -> Option<Pencil> {
+        let wall = self.walls.get(&cell).filter(|_| show.walls());
+        let floor = self.floors.get(&cell).filter(|_| show.floors());
+
+        wall.and_then(|walls| walls.bottom)
+            .map(Pencil::Wall)
+            .or_else(|| floor.map(|&floor| Pencil::Floor(floor)))
+    }
+
+    // Unlinks the instances it covers
     pub fn paste(&mut self, scene: Self, delta: impl Into<Point<i16>>) {
         let delta = delta.into();
 
+        self.unlink(&scene.cells(Show::new()).translate(delta));
+        self.overlay(scene, delta);
+    }
+
+    fn overlay(&mut self, scene: Self, delta: Point<i16>) {
         self.paste_floors(scene.floors, delta);
         self.paste_walls(scene.walls, delta);
     }
+
+    pub fn stamp(&mut self, prefab: &Prefab, delta: Point<i16>, linked: bool) {
+        self.paste(prefab.scene.clone(), delta);
+
+        if linked {
+            self.instances.push(Instance {
+                prefab: prefab.name.clone(),
+                delta,
+                cells: prefab.cells.translate(delta),
+            });
+        }
+    }
+
+    // Paints each cell, a prefab being stamped with its top left corner there.
+    // Unlinks the instances painted over
+    pub fn paint(&mut self, pencil: &Pencil, cells: &Cells, prefabs: &Prefabs, linked: bool) {
+        self.unlink(cells);
+
+        for cell in cells.iter() {
+            let ranges = (cell.x..cell.x + 1, cell.y..cell.y + 1);
+
+            match pencil {
+                &Pencil::Floor((floor, orientation)) => self.add_floor(floor, orientation, ranges),
+                &Pencil::Wall(wall) => self.bottom_wall(Some(wall), ranges),
+                Pencil::Prefab(name) =>
+                    if let Some(prefab) = prefabs.find(name) {
+                        self.stamp(prefab, cell, linked);
+                    },
+            }
+        }
+    }
+
+    // Replaces the linked instances of the prefab with its new version
+    pub fn relink(&mut self, prefab: &Prefab) {
+        for i in 0..self.instances.len() {
+            if self.instances[i].prefab == prefab.name {
+                let delta = self.instances[i].delta;
+                let cells = prefab.cells.translate(delta);
+
+                self.clear(&self.instances[i].cells.clone(), Show::new());
+                self.overlay(prefab.scene.clone(), delta);
+                self.instances[i].cells = cells;
+            }
+        }
+    }
+
+    // Finds the cells of the instances in the library, once the scene is read
+    pub fn link(&mut self, prefabs: &Prefabs) {
+        for instance in &mut self.instances {
+            if let Some(prefab) = prefabs.find(&instance.prefab) {
+                instance.cells = prefab.cells.translate(instance.delta);
+            }
+        }
+    }
+
+    pub fn is_linked(&self, prefab: &str) -> bool {
+        self.instances
+            .iter()
+            .any(|instance| instance.prefab == prefab)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        if let Some(tileset) = &self.tileset {
+            text.push_str(&format!("tileset {}\n", tileset));
+        }
+
+        let mut floors = self.floors.iter().collect::<Vec<_>>();
+        floors.sort_by_key(|(pos, _)| (pos.y, pos.x));
+        for (Point { x, y }, (floor, orientation)) in floors {
+            text.push_str(&format!(
+                "floor {} {} {} {}\n",
+                x,
+                y,
+                floor.name(),
+                orientation.name()
+            ));
+        }
+
+        let mut walls = self
+            .walls
+            .iter()
+            .filter(|(_, walls)| **walls != Walls::default())
+            .collect::<Vec<_>>();
+        walls.sort_by_key(|(pos, _)| (pos.y, pos.x));
+        for (Point { x, y }, walls) in walls {
+            text.push_str(&format!(
+                "walls {} {} {} {} {}\n",
+                x,
+                y,
+                walls.bottom.map(|wall| wall.name()).unwrap_or("-"),
+                walls.left,
+                walls.right
+            ));
+        }
+
+        for Instance {
+            prefab,
+            delta: Point { x, y },
+            ..
+        } in &self.instances
+        {
+            text.push_str(&format!("instance {} {} {}\n", prefab, x, y));
+        }
+
+        let mut bookmarks = self.bookmarks.iter().collect::<Vec<_>>();
+        bookmarks.sort_by_key(|(i, _)| **i);
+        for (i, Bookmark { center, scale }) in bookmarks {
+            text.push_str(&format!(
+                "bookmark {} {} {} {}\n",
+                i, center.x, center.y, scale
+            ));
+        }
+
+        text
+    }
+
+    // Lines that are neither floors, walls, instances nor bookmarks are
+    // ignored
+    pub fn from_text(text: &str) -> Option<Self> {
+        let mut scene = Self::new();
+
+        for line in text.lines() {
+            match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                ["floor", x, y, floor, orientation] => {
+                    scene.floors.insert(
+                        [x.parse().ok()?, y.parse().ok()?].into(),
+                        (
+                            FloorEnum::from_name(floor)?,
+                            Orientation::from_name(orientation)?,
+                        ),
+                    );
+                }
+                ["walls", x, y, bottom, left, right] => {
+                    let bottom = match *bottom {
+                        "-" => None,
+                        bottom => Some(WallEnum::from_name(bottom)?),
+                    };
+
+                    scene.walls.insert(
+                        [x.parse().ok()?, y.parse().ok()?].into(),
+                        Walls::new(bottom, left.parse().ok()?, right.parse().ok()?),
+                    );
+                }
+                ["instance", prefab, x, y] => {
+                    scene.instances.push(Instance {
+                        prefab: prefab.to_string(),
+                        delta:  [x.parse().ok()?, y.parse().ok()?].into(),
+                        cells:  Cells::new(),
+                    });
+                }
+                ["tileset", tileset] => scene.tileset = Some(tileset.to_string()),
+                ["bookmark", i, x, y, scale] => {
+                    scene.bookmarks.insert(i.parse().ok()?, Bookmark {
+                        center: [x.parse().ok()?, y.parse().ok()?].into(),
+                        scale:  scale.parse().ok()?,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Some(scene)
+    }
+
+    // Returns the number of replaced cells
+    pub fn replace(&mut self, replace: Replace, cells: Option<&Cells>) -> usize {
+        let in_cells = |pos: &Point<i16>| cells.map(|cells| cells.contains(pos)).unwrap_or(true);
+        let mut count = 0;
+
+        match replace {
+            Replace::Floor(((from, from_orientation), (to, to_orientation))) =>
+                for (pos, (floor, orientation)) in &mut self.floors {
+                    if in_cells(pos)
+                        && *floor == from
+                        && from_orientation.map(|o| o == *orientation).unwrap_or(true)
+                    {
+                        *floor = to;
+                        *orientation = to_orientation.unwrap_or(*orientation);
+                        count += 1;
+                    }
+                },
+            Replace::Wall((from, to)) =>
+                for (pos, walls) in &mut self.walls {
+                    if in_cells(pos) && walls.bottom == Some(from) {
+                        walls.bottom = Some(to);
+                        count += 1;
+                    }
+                },
+        }
+
+        count
+    }
+
+    pub fn transform(&mut self, cells: &Cells, transform: Transform, show: Show) -> Cells {
+        if let Some(bounds) = cells.bounds() {
+            let cut = self.cut(cells, show);
+            let mut scene = cut.transformed(&bounds, transform);
+            let transformed = cells.transformed(transform);
+
+            // Walls moved onto the top edge of the region belong to the cells
+            // above, which keep their sides
+            for (pos, walls) in &mut scene.walls {
+                if transformed.contains(pos) {
+                    continue;
+                }
+
+                if let Some(existing) = self.walls.get(pos) {
+                    walls.left |= existing.left;
+                    walls.right |= existing.right;
+                }
+            }
+
+            self.paste(scene, [0, 0]);
+        }
+
+        cells.transformed(transform)
+    }
+
+    pub fn transformed(&self, bounds: &(Range<i16>, Range<i16>), transform: Transform) -> Self {
+        let floors = self
+            .floors
+            .iter()
+            .map(|(&pos, &(floor, orientation))| {
+                (
+                    transform.map(pos, bounds),
+                    (floor, transform.orientation(orientation)),
+                )
+            })
+            .collect();
+        let mut walls = SceneWalls::new();
+        for (&pos, &cell) in &self.walls {
+            let pos = transform.map(pos, bounds);
+            let (cell, top_wall) = transform.walls(cell);
+            let merged = walls.entry(pos).or_insert_with(Walls::default);
+
+            merged.bottom = merged.bottom.or(cell.bottom);
+            merged.left |= cell.left;
+            merged.right |= cell.right;
+
+            if let Some(wall) = top_wall {
+                walls.entry(top(pos)).or_insert_with(Walls::default).bottom = Some(wall);
+            }
+        }
+        walls.retain(|_, walls| *walls != Walls::default());
+
+        Self {
+            floors,
+            walls,
+            instances: Vec::new(),
+            bookmarks: HashMap::default(),
+            tileset: self.tileset.clone(),
+        }
+    }
 }
 
 pub fn top(Point { x, y }: Point<i16>) -> Point<i16> {
@@ -28,6 +28,21 @@ impl Show {
         self.grid
     }
 
+    pub fn layers(&self) -> String {
+        let layers = [
+            (self.floors, "Floors"),
+            (self.walls, "Walls"),
+            (self.grid, "Grid"),
+        ];
+
+        layers
+            .iter()
+            .filter(|(show, _)| *show)
+            .map(|(_, name)| *name)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     pub fn show_floors(&mut self) {
         self.floors = true;
     }
@@ -37,30 +52,57 @@ impl Show {
     }
 
     pub fn events(&mut self, keyboard: &Keyboard) {
-        if keyboard.is_pressed(KeyCode::F) {
+        if keyboard.action(Action::ToggleFloors) {
             self.floors = !self.floors;
         }
-        if keyboard.is_pressed(KeyCode::W) {
+        if keyboard.action(Action::ToggleWalls) {
             self.walls = !self.walls;
         }
-        if keyboard.is_pressed(KeyCode::G) {
+        if keyboard.action(Action::ToggleGrid) {
             self.grid = !self.grid;
         }
     }
 }
 
+// Painted with `pencil`, or erased without, over the scene it started on. It
+// is a single undo step, the one of `revision`
+#[derive(Clone, Debug)]
+struct Stroke {
+    pencil:   Option<Pencil>,
+    cells:    Cells,
+    scene:    Scene,
+    revision: Option<usize>,
+}
+
 #[derive(Clone, Debug)]
 pub struct SceneView {
     scene:         History<Scene>,
-    buffer:        Option<(ButtonSelection, Scene)>,
+    buffer:        Option<(Cells, Scene)>,
+    paste:         Option<(Cells, Scene)>,
+    clipboard:     Clipboard,
     pub viewport:  Viewport,
+    split:         Option<Viewport>,
     show:          Show,
+    grid:          Grid,
     pub selection: Selection,
     pub pencil:    Option<Pencil>,
+    pub prefabs:   Prefabs,
+    linked:        bool,
+    prompt:        Option<TextInput>,
+    status:        StatusBar,
+    cursor:        Option<Point>,
+    stroke:        Option<Stroke>,
+    minimap:       Minimap,
+    // Saved with the scene, but not undone
+    bookmarks:     HashMap<u8, Bookmark>,
+    path:          String,
+    saved:         (usize, HashMap<u8, Bookmark>),
+    watch:         Watch,
+    reload:        bool,
 }
 
 impl SceneView {
-    pub fn new(ctx: &mut Context) -> Self {
+    pub fn new(size: Point) -> Self {
         let mut scene = Scene::new();
         scene.make_rects();
         let scene = History::new(scene);
@@ -68,15 +110,249 @@ impl SceneView {
         Self {
             scene,
             buffer: None,
-            viewport: Viewport::new(ctx),
+            paste: None,
+            clipboard: Clipboard::new(),
+            viewport: Viewport::new(size),
+            split: None,
             show: Show::new(),
+            grid: Grid::new(),
             selection: Selection::None,
             pencil: None,
+            prefabs: Prefabs::default(),
+            linked: false,
+            prompt: None,
+            status: StatusBar::new(),
+            cursor: None,
+            stroke: None,
+            minimap: Minimap::new(),
+            bookmarks: HashMap::new(),
+            path: String::from(SCENE),
+            saved: (0, HashMap::new()),
+            watch: Watch::new(Vec::new()),
+            reload: false,
+        }
+    }
+
+    // The grid and prefabs of the working directory, and its scene file
+    // watched until another is opened
+    pub fn load_config(&mut self) {
+        self.grid = Grid::load();
+        self.prefabs = Prefabs::load();
+        self.watch.set_paths(vec![PathBuf::from(&self.path)]);
+    }
+
+    pub fn status(&self) -> &StatusBar {
+        &self.status
+    }
+
+    pub fn message(&mut self, message: String) {
+        self.status.message(message);
+    }
+
+    pub fn update_status(&mut self, delta: Duration) {
+        self.status.update(delta);
+    }
+
+    // How often the scene file is checked for changes
+    pub fn set_watch_interval(&mut self, interval: Duration) {
+        self.watch = Watch::with_interval(vec![PathBuf::from(&self.path)], interval);
+    }
+
+    pub fn scene(&self) -> &Scene {
+        self.scene.get()
+    }
+
+    // The focused pane
+    pub fn viewport(&self) -> &Viewport {
+        &self.viewport
+    }
+
+    // Left to right
+    pub fn panes(&self) -> Vec<Viewport> {
+        let mut panes = std::iter::once(self.viewport)
+            .chain(self.split)
+            .collect::<Vec<_>>();
+        panes.sort_by(|a, b| a.screen().x.partial_cmp(&b.screen().x).unwrap());
+
+        panes
+    }
+
+    // A missing file opens an empty scene, created on save
+    pub fn open(&mut self, path: &str) {
+        let scene = match std::fs::read_to_string(path) {
+            Ok(text) => Scene::from_text(&text),
+            Err(_) => Some(Scene::new()),
+        };
+
+        match scene {
+            Some(mut scene) => {
+                scene.link(&self.prefabs);
+                self.bookmarks = std::mem::take(&mut scene.bookmarks);
+                self.scene.reset(scene);
+                self.path = path.to_string();
+                self.saved = (self.scene.revision(), self.bookmarks.clone());
+                self.watch.set_paths(vec![PathBuf::from(path)]);
+            }
+            None => println!("Cannot read scene {}", path),
+        }
+    }
+
+    pub fn save(&mut self) {
+        let mut scene = self.scene.get().clone();
+        scene.bookmarks = self.bookmarks.clone();
+
+        match std::fs::write(&self.path, scene.to_text()) {
+            Ok(()) => {
+                self.saved = (self.scene.revision(), self.bookmarks.clone());
+                self.watch.forget();
+                self.status.message(format!("Saved {}", self.path));
+            }
+            Err(err) => println!("Cannot save scene {}: {}", self.path, err),
+        }
+    }
+
+    // Undoing back to the saved state counts as unmodified, unless bookmarks
+    // were set since
+    pub fn is_modified(&self) -> bool {
+        let (revision, bookmarks) = &self.saved;
+
+        self.scene.revision() != *revision || self.bookmarks != *bookmarks
+    }
+
+    // Reloads the scene when its file changes, asking first if it was edited
+    pub fn reload(&mut self) {
+        if !self.watch.changed() {
+            return;
+        }
+
+        if self.is_modified() {
+            self.reload = true;
+        } else {
+            let path = self.path.clone();
+            self.open(&path);
+            self.status.message(format!("Reloaded {}", path));
+        }
+    }
+
+    // Bookmarks are numbered from 1
+    fn bookmarks(&mut self, keyboard: &Keyboard) {
+        if let Some(i) = keyboard.action_index(Action::SetBookmark) {
+            let (i, bookmark) = (i as u8 + 1, self.viewport.bookmark());
+
+            self.bookmarks.insert(i, bookmark);
+            self.status.message(format!("Bookmark {} set", i));
+        } else if let Some(i) = keyboard.action_index(Action::Bookmark) {
+            let i = i as u8 + 1;
+
+            match self.bookmarks.get(&i) {
+                Some(&bookmark) => self.viewport.restore(bookmark),
+                None => self.status.message(format!("No bookmark {}", i)),
+            }
+        }
+    }
+
+    // The viewport under the mouse gets the input, unless a button is held
+    pub fn focus(&mut self, mouse: &Mouse) {
+        let position = mouse.position();
+        let held = mouse.left() || mouse.middle() || mouse.right();
+
+        if let Some(other) = self.split.as_mut().filter(|other| other.contains(position)) {
+            if !held {
+                std::mem::swap(&mut self.viewport, other);
+            }
+        }
+    }
+
+    // After the tileset, for both panes
+    pub fn set_cell(&mut self, cell: Point) {
+        for viewport in std::iter::once(&mut self.viewport).chain(&mut self.split) {
+            viewport.set_cell(cell);
         }
     }
 
-    pub fn events(&mut self, ctx: &mut Context, keyboard: &Keyboard, mouse: &Mouse) {
+    // The second pane starts on the same view
+    pub fn toggle_split(&mut self) {
+        self.split = match self.split {
+            Some(_) => None,
+            None => Some(self.viewport),
+        };
+    }
+
+    // Panes share `width`, the right one running under the panel
+    pub fn resize(&mut self, size: Point, width: f32) {
+        let other = match &mut self.split {
+            Some(other) => other,
+            None => return self.viewport.set_screen([0., 0., size.x, size.y].into()),
+        };
+        let (left, right) = if self.viewport.screen().x <= other.screen().x {
+            (&mut self.viewport, other)
+        } else {
+            (other, &mut self.viewport)
+        };
+        let half = (width / 2.).floor();
+
+        left.set_screen([0., 0., half, size.y].into());
+        right.set_screen([half, 0., size.x - half, size.y].into());
+    }
+
+    // Whether the minimap took the mouse
+    pub fn minimap(&mut self, mouse: &Mouse) -> bool {
+        self.minimap
+            .events(mouse, self.scene.get(), &mut self.viewport)
+    }
+
+    pub fn is_typing(&self) -> bool {
+        self.prompt.is_some() || self.reload
+    }
+
+    pub fn set_system_clipboard(&mut self, system: bool) {
+        self.clipboard.set_system(system);
+    }
+
+    pub fn is_pasting(&self) -> bool {
+        self.paste.is_some()
+    }
+
+    pub fn events(&mut self, keyboard: &Keyboard, mouse: &Mouse) {
+        if self.reload {
+            if keyboard.is_pressed(KeyCode::Y) {
+                let path = self.path.clone();
+                self.open(&path);
+                self.status.message(format!("Reloaded {}", path));
+                self.reload = false;
+            } else if keyboard.is_pressed(KeyCode::N) || keyboard.is_pressed(KeyCode::Escape) {
+                self.reload = false;
+            }
+
+            return;
+        }
+
+        if let Some(prompt) = &mut self.prompt {
+            prompt.events(keyboard);
+
+            if keyboard.is_pressed(KeyCode::Return) {
+                let name = prompt.text().to_string();
+                self.prompt = None;
+                self.save_prefab(&name);
+            } else if keyboard.is_pressed(KeyCode::Escape) {
+                self.prompt = None;
+            }
+
+            return;
+        }
+
         self.viewport.handle_keys(keyboard);
+        self.viewport.handle_mouse(mouse);
+        self.bookmarks(keyboard);
+        if keyboard.action(Action::ToggleMinimap) {
+            self.minimap.toggle();
+        }
+        if keyboard.action(Action::ToggleSplit) {
+            self.toggle_split();
+        }
+        if keyboard.action(Action::Save) {
+            self.save();
+        }
         self.show.events(keyboard);
         self.scene.events(keyboard);
 
@@ -86,13 +362,42 @@ impl SceneView {
         } else {
             true
         };
-        self.selection.events(mouse, self.viewport, persist);
+
+        if persist && keyboard.alt() {
+            if let Some(click) = mouse.left_click() {
+                self.wand(keyboard, self.viewport.coordinates_i16(click));
+                return;
+            }
+        }
+
+        self.selection
+            .events(keyboard, mouse, self.viewport, persist);
+    }
+
+    pub fn wand(&mut self, keyboard: &Keyboard, position: Point<i16>) {
+        let wand = self.scene.get().wand(position, self.show);
+
+        self.selection = match self.selection.cells() {
+            Some(cells) if keyboard.shift() => Selection::selected(cells.union(&wand)),
+            Some(cells) if keyboard.ctrl() => Selection::selected(cells.difference(&wand)),
+            _ => Selection::selected(wand),
+        };
     }
 
-    pub fn update(&mut self, ctx: &mut Context, keyboard: &Keyboard, mouse: &Mouse) {
-        self.viewport.set_size(ctx);
+    pub fn update(&mut self, keyboard: &Keyboard, mouse: &Mouse) {
+        if self.is_typing() {
+            return;
+        }
+
+        if let Some(pencil) = self.pencil.clone() {
+            self.paste = None;
+
+            if keyboard.action(Action::Replace) {
+                self.replace(&pencil, mouse, false);
+            } else if keyboard.action(Action::ReplaceOriented) {
+                self.replace(&pencil, mouse, true);
+            }
 
-        if let Some(pencil) = self.pencil {
             match pencil {
                 Pencil::Floor((floor, orientation)) => {
                     self.show.show_floors();
@@ -102,51 +407,349 @@ impl SceneView {
                     self.show.show_walls();
                     self.update_walls(wall);
                 }
+                Pencil::Prefab(prefab) => {
+                    if keyboard.action(Action::ToggleLinked) {
+                        self.linked = !self.linked;
+                    }
+
+                    self.update_prefab(&prefab, mouse);
+                }
             }
+        } else if let Some(paste) = self.paste.take() {
+            self.update_paste(keyboard, mouse, paste);
         } else {
-            if let Selection::Selected(selection) = self.selection {
+            if keyboard.action(Action::SelectAll) {
+                self.selection = Selection::selected(self.scene.get().cells(self.show));
+            } else if keyboard.action(Action::InvertSelection) {
+                let cells = self.selection.cells().cloned().unwrap_or_default();
+                let scene = self.scene.get().cells(self.show);
+
+                if let Some(bounds) = scene.union(&cells).bounds() {
+                    self.selection =
+                        Selection::selected(Cells::from_ranges(bounds).difference(&cells));
+                }
+            } else if let Selection::Selected(cells) = self.selection.clone() {
                 let show = self.show;
 
-                if keyboard.is_pressed(KeyCode::R) {
-                    self.scene.edit(|scene| {
-                        scene.rotate_floor(
-                            selection.ranges(),
-                            if keyboard.shift() {
-                                Orientation::rotate_left
-                            } else {
-                                Orientation::rotate_right
-                            },
-                        )
-                    });
-                } else if keyboard.is_pressed(KeyCode::Delete) {
-                    self.scene
-                        .edit(|scene| scene.remove(selection.ranges(), show));
+                if let Some(transform) = Transform::from_keyboard(keyboard) {
+                    let cells = self
+                        .scene
+                        .edit(|scene| scene.transform(&cells, transform, show));
+                    self.selection = Selection::selected(cells);
+                } else if keyboard.action(Action::Delete) {
+                    self.scene.edit(|scene| scene.remove(&cells, show));
                     self.selection.clear();
-                } else if keyboard.ctrl() && keyboard.is_pressed(KeyCode::X) {
-                    self.buffer = Some((
-                        selection,
-                        self.scene.edit(|scene| scene.cut(selection.ranges(), show)),
-                    ));
+                } else if keyboard.action(Action::Cut) {
+                    let cut = self.scene.edit(|scene| scene.cut(&cells, show));
+                    let buffer = (cells, cut);
+                    self.clipboard.copy(&buffer);
+                    self.buffer = Some(buffer);
+                    self.selection.clear();
+                } else if keyboard.action(Action::Copy) {
+                    let buffer = (cells.clone(), self.scene.get().copy(&cells, show));
+                    self.clipboard.copy(&buffer);
+                    self.buffer = Some(buffer);
                     self.selection.clear();
-                } else if keyboard.ctrl() && keyboard.is_pressed(KeyCode::C) {
-                    self.buffer =
-                        Some((selection, self.scene.get().copy(selection.ranges(), show)));
+                } else if keyboard.action(Action::SavePrefab) {
+                    self.prompt = Some(TextInput::new());
+                }
+            }
+
+            if keyboard.action(Action::Paste) {
+                if let Some(buffer) = self.clipboard.paste().or_else(|| self.buffer.clone()) {
+                    self.paste = Some(buffer);
                     self.selection.clear();
                 }
             }
+        }
+    }
+
+    // The left stick moves a cell cursor, shown once used, which South paints
+    // and East erases, a held button making a single stroke. Erasing while
+    // painting (or changing the pencil) starts another one
+    pub fn gamepad(&mut self, gamepad: &Gamepad) {
+        if self.is_typing() {
+            return;
+        }
+
+        let Point { x, y } = gamepad.right_stick();
+        self.viewport
+            .translate([x * GAMEPAD_PAN_SPEED, y * GAMEPAD_PAN_SPEED]);
+
+        let center = self.viewport.center();
+        let zoom = self.viewport.scale() * GAMEPAD_ZOOM_SPEED;
+        if gamepad.is_held(Button::RightTrigger2) {
+            self.viewport.zoom(center, zoom);
+        } else if gamepad.is_held(Button::LeftTrigger2) {
+            self.viewport.zoom(center, -zoom);
+        }
+
+        let stick = gamepad.left_stick();
+        let faces = [Button::South, Button::East, Button::West];
+        if self.cursor.is_none()
+            && stick == [0., 0.].into()
+            && !faces.iter().any(|&button| gamepad.is_pressed(button))
+        {
+            return;
+        }
+
+        let mut cursor = self
+            .cursor
+            .unwrap_or_else(|| self.viewport.coordinates(center));
+        cursor.x += stick.x * GAMEPAD_CURSOR_SPEED;
+        cursor.y += stick.y * GAMEPAD_CURSOR_SPEED;
+        self.cursor = Some(cursor);
+        if stick != [0., 0.].into() {
+            self.follow(cursor);
+        }
+
+        if gamepad.is_pressed(Button::West) {
+            if let Some(Pencil::Floor((_, orientation))) = &mut self.pencil {
+                orientation.rotate_right();
+            }
+        }
 
-            if keyboard.ctrl() && keyboard.is_pressed(KeyCode::V) {
-                if let Some((selection, buffer)) = &self.buffer {
-                    let position = self.viewport.coordinates_i16(mouse.position());
-                    let start = selection.into_i16().start();
-                    let dx = position.x - start.x;
-                    let dy = position.y - start.y;
+        let pencil = match &self.pencil {
+            _ if gamepad.is_held(Button::East) => None,
+            Some(pencil) if gamepad.is_held(Button::South) => Some(pencil.clone()),
+            _ => {
+                self.stroke = None;
+                return;
+            }
+        };
+
+        // Undoing also ends the stroke
+        let revision = self.scene.revision();
+        let cell = cursor.into_i16();
+        let mut stroke = match self
+            .stroke
+            .take()
+            .filter(|stroke| stroke.pencil == pencil && stroke.revision == Some(revision))
+        {
+            Some(stroke) if stroke.cells.contains(&cell) => {
+                self.stroke = Some(stroke);
+                return;
+            }
+            Some(stroke) => stroke,
+            None => Stroke {
+                pencil,
+                cells: Cells::new(),
+                scene: self.scene.get().clone(),
+                revision: None,
+            },
+        };
+        stroke.cells.insert(cell);
 
-                    self.selection = Selection::Selected(selection.translate([dx as _, dy as _]));
+        let mut scene = stroke.scene.clone();
+        match &stroke.pencil {
+            Some(pencil) => scene.paint(pencil, &stroke.cells, &self.prefabs, self.linked),
+            None => scene.remove(&stroke.cells, self.show),
+        }
+        match stroke.revision {
+            Some(_) => self.scene.amend(scene),
+            None => self.scene.edit(|current| *current = scene),
+        }
+        stroke.revision = Some(self.scene.revision());
+        self.stroke = Some(stroke);
+    }
+
+    // Pans so that the moving cursor stays a cell away from the edges
+    fn follow(&mut self, cursor: Point) {
+        let tile = self.viewport.tile();
+        let x = cursor.x * tile.x - self.viewport.x();
+        let y = cursor.y * tile.y - self.viewport.y();
+        let (w, h) = (self.viewport.w(), self.viewport.h() - StatusBar::HEIGHT);
+
+        let dx = (x - tile.x).min(0.) + (x + 2. * tile.x - w).max(0.);
+        let dy = (y - tile.y).min(0.) + (y + 2. * tile.y - h).max(0.);
+        self.viewport.translate([dx, dy]);
+    }
+
+    // Fits the visible scene, or the selection, in `area`
+    pub fn fit(&mut self, area: Rect, selection: bool) {
+        let cells = if selection {
+            self.selection.cells().cloned()
+        } else {
+            Some(self.scene.get().cells(self.show))
+        };
+
+        if let Some(bounds) = cells.and_then(|cells| cells.bounds()) {
+            self.viewport.fit(bounds, area);
+        }
+    }
+
+    // Items apply to the selection when it holds `cell`, to `cell` otherwise
+    // A click outside the selection clears it, the menu then acting on the
+    // clicked cell
+    pub fn context_menu(&mut self, position: Point) -> ContextMenu {
+        let cell = self.viewport.coordinates_i16(position);
+        if !self
+            .selection
+            .cells()
+            .map_or(false, |cells| cells.contains(&cell))
+        {
+            self.selection.clear();
+        }
+
+        let scene = self.scene.get();
+        let cells = self.targets(cell);
+        let content = scene.cells(self.show);
+        let items = ContextMenu::items(
+            scene.pick(cell, self.show).is_some(),
+            cells.iter().any(|cell| content.contains(&cell)),
+            matches!(self.pencil, Some(Pencil::Floor(_) | Pencil::Wall(_))),
+        );
+
+        let screen = self.viewport.screen();
+        ContextMenu::new(
+            position,
+            cell,
+            items,
+            [screen.right(), screen.bottom()].into(),
+        )
+    }
+
+    fn targets(&self, cell: Point<i16>) -> Cells {
+        match self.selection.cells() {
+            Some(cells) if cells.contains(&cell) => cells.clone(),
+            _ => Cells::from_iter(vec![cell]),
+        }
+    }
+
+    pub fn menu_item(&mut self, item: MenuItem, cell: Point<i16>) {
+        let show = self.show;
+        let selected = self
+            .selection
+            .cells()
+            .map_or(false, |cells| cells.contains(&cell));
+        let cells = self.targets(cell);
+
+        match item {
+            MenuItem::Pick =>
+                if let Some(pencil) = self.scene.get().pick(cell, show) {
+                    self.pencil = Some(pencil);
+                },
+            MenuItem::Delete => {
+                self.scene.edit(|scene| scene.remove(&cells, show));
+                if selected {
+                    self.selection.clear();
+                }
+            }
+            MenuItem::Rotate => {
+                let cells = self
+                    .scene
+                    .edit(|scene| scene.transform(&cells, Transform::Rotate90, show));
+                if selected {
+                    self.selection = Selection::selected(cells);
+                }
+            }
+            MenuItem::Copy => {
+                let buffer = (cells.clone(), self.scene.get().copy(&cells, show));
+                self.clipboard.copy(&buffer);
+                self.buffer = Some(buffer);
+            }
+            MenuItem::Fill => {
+                let cells = if selected {
+                    cells
+                } else {
+                    self.scene.get().region(cell, show)
+                };
+                let (prefabs, linked) = (&self.prefabs, self.linked);
+
+                if let Some(pencil) = &self.pencil {
                     self.scene
-                        .edit(|scene| scene.paste(buffer.clone(), [dx, dy]));
+                        .edit(|scene| scene.paint(pencil, &cells, prefabs, linked));
+                }
+            }
+        }
+    }
+
+    pub fn replace(&mut self, pencil: &Pencil, mouse: &Mouse, orientation: bool) {
+        let position = self.viewport.coordinates_i16(mouse.position());
+        let replace = Replace::new(self.scene.get(), position, pencil, orientation, self.show);
+
+        match replace {
+            Some(replace) => {
+                let mut scene = self.scene.get().clone();
+                let count = scene.replace(replace, self.selection.cells());
+
+                if count > 0 {
+                    self.scene.edit(|current| *current = scene);
                 }
+                self.status.message(format!("Replaced {} cells", count));
             }
+            None => self.status.message(String::from("Nothing to replace here")),
+        }
+    }
+
+    pub fn save_prefab(&mut self, name: &str) {
+        let cells = match self.selection.cells() {
+            Some(cells) => cells,
+            None => return,
+        };
+        let prefab = match Prefab::new(name, cells, self.scene.get().copy(cells, self.show)) {
+            Some(prefab) => prefab,
+            None => return,
+        };
+
+        match self.prefabs.save(prefab.clone()) {
+            Ok(()) =>
+                if self.scene.get().is_linked(&prefab.name) {
+                    self.scene.edit(|scene| scene.relink(&prefab));
+                },
+            Err(err) => self
+                .status
+                .message(format!("Cannot save prefab {}: {}", prefab.name, err)),
+        }
+    }
+
+    pub fn update_prefab(&mut self, name: &str, mouse: &Mouse) {
+        let prefab = match self.prefabs.find(name) {
+            Some(prefab) => prefab,
+            None => return,
+        };
+
+        if mouse.left_click().is_some() {
+            let delta = self.viewport.magnetize(mouse.position()).into_i16();
+            let linked = self.linked;
+
+            self.scene.edit(|scene| scene.stamp(prefab, delta, linked));
+        }
+    }
+
+    pub fn update_paste(
+        &mut self,
+        keyboard: &Keyboard,
+        mouse: &Mouse,
+        (cells, paste): (Cells, Scene),
+    ) {
+        if keyboard.action(Action::CancelPaste) {
+            return;
+        }
+
+        if let Some(transform) = Transform::from_keyboard(keyboard) {
+            if let Some(bounds) = cells.bounds() {
+                let paste = paste.transformed(&bounds, transform);
+                self.paste = Some((cells.transformed(transform), paste));
+            }
+        } else if mouse.left_click().is_some() {
+            let delta = self.paste_delta(&cells, mouse.position());
+
+            self.selection = Selection::selected(cells.translate(delta));
+            self.scene.edit(|scene| scene.paste(paste, delta));
+        } else {
+            self.paste = Some((cells, paste));
+        }
+    }
+
+    // Anchors the top left corner of the pasted cells under the cursor
+    fn paste_delta(&self, cells: &Cells, position: Point) -> Point<i16> {
+        let position = self.viewport.magnetize(position).into_i16();
+        let (x, y) = cells.bounds().unwrap_or_default();
+
+        Point {
+            x: position.x - x.start,
+            y: position.y - y.start,
         }
     }
 
@@ -186,34 +789,195 @@ impl SceneView {
         }
     }
 
-    pub fn draw(&mut self, mut ctx: &mut Context, tile_renderer: &mut TileRenderer, mouse: &Mouse) {
+    fn draw_ghost(
+        &self,
+        tile_renderer: &mut TileRenderer,
+        scene: &Scene,
+        delta: Point<i16>,
+        color: Color,
+    ) {
+        let mut ghost = Scene::new();
+        ghost.paste(scene.clone(), delta);
+
+        tile_renderer.set_color(color);
+        ghost.render(tile_renderer, self.show);
+        tile_renderer.set_color(Color::WHITE);
+    }
+
+    pub fn draw(&mut self, ctx: &mut Context, tile_renderer: &mut TileRenderer, mouse: &Mouse) {
+        tile_renderer.set_tileset(ctx, self.scene.get().tileset());
         self.scene.get().render(tile_renderer, self.show);
 
-        if let Some(pencil) = self.pencil {
-            if Selection::None == self.selection {
+        if let Some(pencil) = &self.pencil {
+            if self.selection.selection().is_none() {
                 pencil.draw(tile_renderer, mouse.position(), &self.viewport);
             }
         }
 
-        tile_renderer.draw(ctx, self.viewport.origin(), self.viewport.scale());
+        if let (Some(pencil), Some(cursor)) = (&self.pencil, self.cursor) {
+            let (origin, tile) = (self.viewport.origin(), self.viewport.tile());
+            let Point { x, y } = cursor.into_i16();
+            let center = [
+                origin.x + (x as f32 + 0.5) * tile.x,
+                origin.y + (y as f32 + 0.5) * tile.y,
+            ];
+            pencil.draw(tile_renderer, center.into(), &self.viewport);
+        }
+
+        let prefab = match &self.pencil {
+            Some(Pencil::Prefab(name)) => self.prefabs.find(name),
+            _ => None,
+        };
+        if let Some(prefab) = prefab.filter(|_| self.selection.selection().is_none()) {
+            let delta = self.viewport.magnetize(mouse.position()).into_i16();
+            let color = if self.linked {
+                Color::new(0.5, 1., 0.5, 0.5)
+            } else {
+                Color::new(1., 1., 1., 0.5)
+            };
+
+            self.draw_ghost(tile_renderer, &prefab.scene, delta, color);
+        }
+
+        let paste = self.paste.as_ref().map(|(cells, paste)| {
+            let delta = self.paste_delta(cells, mouse.position());
+            self.draw_ghost(tile_renderer, paste, delta, Color::new(1., 1., 1., 0.5));
+
+            cells.translate(delta)
+        });
+
+        // Both panes show the same sprites, from their own viewports
+        let panes = std::iter::once(self.viewport).chain(self.split);
+        for viewport in panes.collect::<Vec<_>>() {
+            if self.split.is_some() {
+                set_scissor_rect(ctx, Some(viewport.screen())).unwrap();
+            }
+
+            self.draw_pane(ctx, tile_renderer, viewport, paste.as_ref());
+        }
+
+        if let Some(other) = self.split {
+            set_scissor_rect(ctx, None).unwrap();
+
+            let x = self.viewport.screen().x.max(other.screen().x);
+            MeshBuilder::new()
+                .line(
+                    &[[x, 0.], [x, self.viewport.h()]],
+                    2.,
+                    Color::new(0.3, 0.3, 0.3, 1.),
+                )
+                .unwrap()
+                .build(ctx)
+                .unwrap()
+                .draw(ctx, DrawParam::new())
+                .unwrap();
+        }
+
+        let y = self.viewport.h() - StatusBar::HEIGHT - 30.;
+        if let Some(prompt) = &self.prompt {
+            prompt.draw(ctx, "Prefab name", [10., y]);
+        } else if self.reload {
+            let question = format!(
+                "{} changed on disk, reload it and lose the edits? (Y/N)",
+                self.path
+            );
+            Text::new(question)
+                .draw(ctx, DrawParam::new().dest([10., y]))
+                .unwrap();
+        }
+    }
+
+    fn draw_pane(
+        &mut self,
+        mut ctx: &mut Context,
+        tile_renderer: &mut TileRenderer,
+        viewport: Viewport,
+        paste: Option<&Cells>,
+    ) {
+        tile_renderer.draw(ctx, viewport.origin(), viewport.scale());
 
         if self.show.grid() {
-            Grid::draw(ctx, self.viewport);
+            self.grid.draw(ctx, viewport);
+        }
+
+        if let Some(paste) = paste {
+            paste.draw(ctx, viewport);
+        }
+
+        if let Some(cells) = self.selection.cells() {
+            cells.draw(ctx, viewport);
+        }
+
+        if let Some(cursor) = self.cursor {
+            let tile = viewport.tile();
+            let origin = viewport.origin();
+            let Point { x, y } = cursor.into_i16();
+            let cursor = Rect::new(
+                origin.x + x as f32 * tile.x,
+                origin.y + y as f32 * tile.y,
+                tile.x,
+                tile.y,
+            );
+
+            MeshBuilder::new()
+                .rectangle(DrawMode::stroke(2.), cursor, Color::new(1., 1., 0., 1.))
+                .unwrap()
+                .build(ctx)
+                .unwrap()
+                .draw(ctx, DrawParam::new())
+                .unwrap();
         }
 
+        self.minimap
+            .draw(ctx, self.scene.get(), self.scene.revision(), viewport);
+
         if let Some(selection) = self.selection.selection() {
             match self.pencil {
                 Some(Pencil::Wall(_)) => thirds((
                     selection,
                     ctx,
-                    |ctx| selection.draw_vertical(ctx, self.viewport),
-                    |ctx| selection.draw_horizontal(ctx, self.viewport),
-                    |ctx| selection.draw_vertical(ctx, self.viewport),
+                    |ctx| selection.draw_vertical(ctx, viewport),
+                    |ctx| selection.draw_horizontal(ctx, viewport),
+                    |ctx| selection.draw_vertical(ctx, viewport),
                 )),
-                _ => selection.draw(ctx, self.viewport),
+                _ => selection.draw(ctx, viewport),
             }
         }
     }
+
+    pub fn draw_status(&self, ctx: &mut Context, mouse: &Mouse) {
+        let size = |(x, y): (Range<i16>, Range<i16>)| format!("{}x{}", x.len(), y.len());
+        let Point { x, y } = self.viewport.coordinates_i16(mouse.position());
+        let mut fields = vec![format!("Cell {}, {}", x, y)];
+
+        if let Some(selection) = self.selection.selection() {
+            fields.push(format!("Selecting {}", size(selection.ranges())));
+        } else if let Some(cells) = self.selection.cells() {
+            let bounds = cells.bounds().unwrap_or_default();
+            fields.push(format!(
+                "Selection {} ({} cells)",
+                size(bounds),
+                cells.len()
+            ));
+        }
+
+        let tool = match (&self.pencil, &self.paste) {
+            (Some(pencil), _) => format!("Pencil {}", pencil.name()),
+            (None, Some((cells, _))) =>
+                format!("Paste {}", size(cells.bounds().unwrap_or_default())),
+            (None, None) => String::from("Select"),
+        };
+
+        fields.push(format!(
+            "Zoom {:.0}%{}",
+            self.viewport.scale() * 100.,
+            if self.viewport.snap() { " (snap)" } else { "" }
+        ));
+        fields.push(tool);
+        fields.push(format!("Layers: {}", self.show.layers()));
+
+        self.status.draw(ctx, &fields);
+    }
 }
 
 pub fn thirds<T: ThirdsArgs>(args: T) -> T::Output {
@@ -0,0 +1,58 @@
+use super::*;
+
+// The message is aged by frame times, so that replays show it as long
+#[derive(Clone, Debug)]
+pub struct StatusBar {
+    message: Option<(String, Duration)>,
+}
+
+impl StatusBar {
+    pub const HEIGHT: f32 = 24.;
+    const MESSAGE: Duration = Duration::from_secs(3);
+
+    pub fn new() -> Self {
+        Self { message: None }
+    }
+
+    pub fn message(&mut self, message: String) {
+        self.message = Some((message, Duration::from_secs(0)));
+    }
+
+    // Messages are shown for a few seconds
+    pub fn update(&mut self, delta: Duration) {
+        if let Some((_, age)) = &mut self.message {
+            *age += delta;
+        }
+        self.message = self.message.take().filter(|(_, age)| *age < Self::MESSAGE);
+    }
+
+    pub fn last_message(&self) -> Option<&str> {
+        self.message.as_ref().map(|(message, _)| message.as_str())
+    }
+
+    // The message follows the fields
+    pub fn draw(&self, ctx: &mut Context, fields: &[String]) {
+        let (w, h) = drawable_size(ctx);
+        let mut text = fields.join("  |  ");
+
+        if let Some(message) = self.last_message() {
+            text.push_str("  |  ");
+            text.push_str(message);
+        }
+
+        MeshBuilder::new()
+            .rectangle(
+                DrawMode::fill(),
+                [0., h - Self::HEIGHT, w, Self::HEIGHT].into(),
+                Color::new(0.1, 0.1, 0.1, 1.),
+            )
+            .unwrap()
+            .build(ctx)
+            .unwrap()
+            .draw(ctx, DrawParam::new())
+            .unwrap();
+        Text::new(text)
+            .draw(ctx, DrawParam::new().dest([8., h - Self::HEIGHT + 5.]))
+            .unwrap();
+    }
+}
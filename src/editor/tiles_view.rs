@@ -1,175 +1,439 @@
 use super::*;
 
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Entry {
+    Element(Element),
+    Prefab(usize),
+}
+
+impl Entry {
+    fn name<'a>(&self, prefabs: &'a Prefabs) -> &'a str {
+        match *self {
+            Self::Element(element) => element.name(),
+            Self::Prefab(i) => prefabs
+                .get(i)
+                .map(|prefab| prefab.name.as_str())
+                .unwrap_or_default(),
+        }
+    }
+
+    // Prefab thumbnails are drawn at half the palette scale
+    fn size(&self, prefabs: &Prefabs, catalogue: &Catalogue, scale: f32) -> Point {
+        match *self {
+            Self::Element(element) => {
+                let tile = catalogue.tile(element);
+
+                [scale * tile.w as f32, scale * tile.h as f32].into()
+            }
+            Self::Prefab(i) => {
+                let size = prefabs
+                    .get(i)
+                    .map(|prefab| prefab.size())
+                    .unwrap_or_default();
+                let cell = catalogue.cell();
+
+                [
+                    scale / 2. * size.x as f32 * cell.x,
+                    scale / 2. * size.y as f32 * cell.y,
+                ]
+                .into()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Row {
+    y:       f32,
+    h:       f32,
+    entries: Vec<(Entry, Rect)>,
+}
+
+impl Row {
+    fn new(y: f32) -> Self {
+        Self {
+            y,
+            h: 0.,
+            entries: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, entry: Entry, rect: Rect) {
+        self.entries.push((entry, rect));
+        self.h = self.h.max(rect.h);
+    }
+
+    // Entries of different heights stand on the bottom of the row
+    fn aligned(mut self) -> Self {
+        for (_, rect) in &mut self.entries {
+            rect.y = self.y + self.h - rect.h;
+        }
+
+        self
+    }
+}
+
+// Positions are those of the unscrolled palette
+#[derive(Clone, Default, Debug)]
+struct Layout {
+    labels: Vec<(&'static str, Point)>,
+    rows:   Vec<Row>,
+    height: f32,
+}
+
 #[derive(Clone, Debug)]
 pub struct TilesView {
-    selected: Option<usize>,
+    selected: Option<Entry>,
+    recent:   Vec<Entry>,
+    filter:   TextInput,
+    scroll:   f32,
+    scale:    f32,
+    rect:     Rect,
+    changed:  bool,
 }
 
 impl TilesView {
+    const HEADER: f32 = 64.;
+    const LABEL: f32 = 24.;
+    const MARGIN: f32 = 16.;
+    const RECENT: usize = 12;
     const SCALE: f32 = 2.;
+    const SCALES: (f32, f32) = (1., 6.);
+    const SPACING: f32 = 8.;
 
-    pub fn new(ctx: &mut Context) -> Self {
-        Self { selected: None }
+    pub fn new(size: Point) -> Self {
+        Self {
+            selected: None,
+            recent:   Vec::new(),
+            filter:   TextInput::new(),
+            scroll:   0.,
+            scale:    Self::SCALE,
+            rect:     [0., 0., size.x, size.y].into(),
+            changed:  false,
+        }
     }
 
-    pub fn events(&mut self, ctx: &mut Context, keyboard: &Keyboard, mouse: &Mouse) {
-        if let Some(pos) = mouse.left_click() {
-            for (i, &(Point { x, y }, element)) in Self::ELEMENTS.iter().enumerate() {
-                let tile = element.tile();
-                let x = x * TILE_WIDTH;
-                let y = y * TILE_HEIGHT;
-                let width = Self::SCALE * tile.w as f32;
-                let height = Self::SCALE * tile.h as f32;
+    // The palette is laid out in `rect`, either the whole window or a panel
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
 
-                if x <= pos.x && pos.x <= x + width {
-                    if y <= pos.y && pos.y <= y + height {
-                        self.selected = Some(i);
-                        break;
-                    }
-                }
-                self.selected = None;
+    pub fn contains(&self, position: Point) -> bool {
+        self.rect.contains(position)
+    }
+
+    pub fn is_filtering(&self) -> bool {
+        !self.filter.text().is_empty()
+    }
+
+    pub fn events(
+        &mut self,
+        keyboard: &Keyboard,
+        mouse: &Mouse,
+        prefabs: &Prefabs,
+        catalogue: &Catalogue,
+    ) {
+        let filter = self.filter.text().to_string();
+        self.filter.events(keyboard);
+        if keyboard.is_pressed(KeyCode::Escape) {
+            self.filter = TextInput::new();
+        }
+        if self.filter.text() != filter {
+            self.scroll = 0.;
+        }
+
+        let layout = self.layout(prefabs, catalogue);
+        self.scroll = self.scroll.min(layout.height - self.rect.h).max(0.);
+
+        if let Some(position) = mouse.left_click() {
+            self.selected = self.entry_at(&layout, position);
+            self.changed = true;
+
+            if let Some(selected) = self.selected {
+                self.recent.retain(|entry| *entry != selected);
+                self.recent.insert(0, selected);
+                self.recent.truncate(Self::RECENT);
             }
         }
     }
 
-    pub fn update(&mut self, ctx: &mut Context, pencil: &mut Option<Pencil>) {
-        if let Some(selected) = self.selected {
-            match Self::ELEMENTS[selected].1 {
-                Element::Floor(floor) => {
-                    *pencil = Some(Pencil::Floor((floor, North)));
-                }
-                Element::Wall(wall) => {
-                    *pencil = Some(Pencil::Wall(wall));
-                }
-            }
+    // Scrolls, or zooms with `zoom`
+    pub fn wheel(&mut self, y: f32, zoom: bool) {
+        if zoom {
+            let (min, max) = Self::SCALES;
+            self.scale = (self.scale + y / 2.).max(min).min(max);
         } else {
-            *pencil = None;
+            self.scroll += y * SCROLL_SENSITIVITY.y;
+        }
+    }
+
+    // The pencil only changes on clicks, keeping its orientation otherwise
+    pub fn update(&mut self, pencil: &mut Option<Pencil>, prefabs: &Prefabs) {
+        if !std::mem::take(&mut self.changed) {
+            return;
         }
+
+        *pencil = self.selected.and_then(|entry| Self::pencil(entry, prefabs));
+    }
+
+    // Selects the entry `delta` away from the selected one, ignoring the
+    // filter, and returns its pencil
+    pub fn cycle(&mut self, delta: isize, prefabs: &Prefabs) -> Option<Pencil> {
+        let mut entries = Element::categories()
+            .iter()
+            .flat_map(|(_, elements)| elements.clone())
+            .map(Entry::Element)
+            .collect::<Vec<_>>();
+        entries.extend((0..prefabs.len()).map(Entry::Prefab));
+        entries.retain(|&entry| Self::pencil(entry, prefabs).is_some());
+
+        let len = entries.len() as isize;
+        let i = match self.selected {
+            Some(selected) => entries
+                .iter()
+                .position(|entry| *entry == selected)
+                .map_or(0, |i| (i as isize + delta).rem_euclid(len)),
+            None if delta < 0 => len - 1,
+            None => 0,
+        };
+        self.selected = entries.get(i as usize).copied();
+
+        self.selected.and_then(|entry| Self::pencil(entry, prefabs))
     }
 
-    pub fn draw(&mut self, ctx: &mut Context, tile_renderer: &mut TileRenderer) {
-        for &(point, element) in Self::ELEMENTS {
-            tile_renderer.add((element.tile(), point, Self::SCALE));
+    // Creatures and items have no layer in the scene, they are only listed
+    fn pencil(entry: Entry, prefabs: &Prefabs) -> Option<Pencil> {
+        match entry {
+            Entry::Element(Element::Floor(floor)) => Some(Pencil::Floor((floor, North))),
+            Entry::Element(Element::Wall(wall)) => Some(Pencil::Wall(wall)),
+            Entry::Element(Element::Creature(_) | Element::Item(_)) => None,
+            Entry::Prefab(i) => prefabs
+                .get(i)
+                .map(|prefab| Pencil::Prefab(prefab.name.clone())),
         }
+    }
 
+    pub fn draw(
+        &mut self,
+        ctx: &mut Context,
+        tile_renderer: &mut TileRenderer,
+        mouse: &Mouse,
+        prefabs: &Prefabs,
+        pencil: Option<&Pencil>,
+    ) {
+        let layout = self.layout(prefabs, tile_renderer.catalogue());
+        let scroll = self.scroll;
+        let Rect { x, y, w, h } = self.rect;
+        let entries = layout
+            .rows
+            .iter()
+            .filter(|row| row.y + row.h - scroll >= y + Self::HEADER && row.y - scroll <= y + h)
+            .flat_map(|row| row.entries.iter().copied())
+            .collect::<Vec<_>>();
+
+        let cell = tile_renderer.catalogue().cell();
+        for &(entry, rect) in &entries {
+            if let Entry::Element(element) = entry {
+                let position = [rect.x / cell.x, (rect.y - scroll) / cell.y];
+                tile_renderer.add((tile_renderer.tile(element), position, self.scale));
+            }
+        }
         tile_renderer.draw(ctx, [0., 0.], 1.);
 
+        // Prefab scenes start at the origin, each one is drawn on its own
+        for &(entry, rect) in &entries {
+            if let Some(prefab) = match entry {
+                Entry::Prefab(i) => prefabs.get(i),
+                _ => None,
+            } {
+                tile_renderer.clear();
+                prefab.scene.render(tile_renderer, Show::new());
+                tile_renderer.draw(ctx, [rect.x, rect.y - scroll], self.scale / 2.);
+            }
+        }
+
+        for &(label, Point { x, y }) in &layout.labels {
+            Text::new(label)
+                .draw(ctx, DrawParam::new().dest([x, y - scroll]))
+                .unwrap();
+        }
+
         if let Some(selected) = self.selected {
-            let (Point { x, y }, element) = Self::ELEMENTS[selected];
-            let tile = element.tile();
-            let width = Self::SCALE * tile.w as f32;
-            let height = Self::SCALE * tile.h as f32;
             let green = Color::new(0., 1., 0., 1.);
+            let mut mesh = MeshBuilder::new();
+            let mut empty = true;
+
+            let rects = entries.iter().filter(|(entry, _)| *entry == selected);
 
-            MeshBuilder::new()
-                .rectangle(
+            for &(_, Rect { x, y, w, h }) in rects {
+                mesh.rectangle(
                     DrawMode::stroke(2.),
-                    [
-                        x * TILE_WIDTH - 1.,
-                        y * TILE_HEIGHT - 1.,
-                        width + 2.,
-                        height + 2.,
-                    ]
-                    .into(),
+                    [x - 1., y - scroll - 1., w + 2., h + 2.].into(),
                     green,
                 )
-                .unwrap()
-                .build(ctx)
-                .unwrap()
-                .draw(ctx, DrawParam::new().dest([0., 0.]))
                 .unwrap();
+                empty = false;
+            }
+
+            if !empty {
+                mesh.build(ctx)
+                    .unwrap()
+                    .draw(ctx, DrawParam::new())
+                    .unwrap();
+            }
+        }
+
+        // The header stays on top of the scrolled palette
+        MeshBuilder::new()
+            .rectangle(
+                DrawMode::fill(),
+                [x, y, w, Self::HEADER].into(),
+                Color::BLACK,
+            )
+            .unwrap()
+            .build(ctx)
+            .unwrap()
+            .draw(ctx, DrawParam::new())
+            .unwrap();
+        let (x, y) = (x + Self::MARGIN, y + Self::MARGIN / 2.);
+        self.filter.draw(ctx, "Filter", [x, y]);
+        Self::draw_pencil(ctx, tile_renderer, pencil, [x, y + Self::LABEL].into());
+
+        if let Some(entry) = self.entry_at(&layout, mouse.position()) {
+            Self::tooltip(ctx, entry.name(prefabs), mouse.position());
         }
     }
-}
 
-impl TilesView {
-    const ELEMENTS: &'static [(Point, Element)] = &[
-        // Floors
-        (Point { x: 1., y: 1. }, Element::Floor(Floor)),
-        (Point { x: 4., y: 1. }, Element::Floor(Cracks1)),
-        (Point { x: 7., y: 1. }, Element::Floor(Cracks2)),
-        (Point { x: 10., y: 1. }, Element::Floor(Cracks3)),
-        (Point { x: 13., y: 1. }, Element::Floor(Cracks4)),
-        (Point { x: 16., y: 1. }, Element::Floor(Cracks5)),
-        (Point { x: 19., y: 1. }, Element::Floor(Cracks6)),
-        (Point { x: 22., y: 1. }, Element::Floor(Cracks7)),
-        (Point { x: 25., y: 1. }, Element::Floor(Ladder)),
-        (Point { x: 28., y: 1. }, Element::Floor(Spikes)),
-        (Point { x: 31., y: 1. }, Element::Floor(Hole)),
-        (Point { x: 34., y: 1. }, Element::Floor(Edge)),
-        (Point { x: 1., y: 4. }, Element::Wall(Wall)),
-        (Point { x: 4., y: 4. }, Element::Wall(SmallHole)),
-        (Point { x: 7., y: 4. }, Element::Wall(BigHole)),
-        (Point { x: 10., y: 4. }, Element::Wall(RedBanner)),
-        (Point { x: 13., y: 4. }, Element::Wall(GreenBanner)),
-        (Point { x: 16., y: 4. }, Element::Wall(BlueBanner)),
-        (Point { x: 19., y: 4. }, Element::Wall(YellowBanner)),
-        (Point { x: 22., y: 4. }, Element::Wall(LavaFountain)),
-        (Point { x: 25., y: 4. }, Element::Wall(WaterFountain)),
-        (Point { x: 28., y: 4. }, Element::Wall(Goo)),
-        /*
-        (Point { x: 1., y: 7. }, Tile::ELF_M_IDLE_ANIM_0),
-        (Point { x: 4., y: 7. }, Tile::ELF_F_IDLE_ANIM_0),
-        (Point { x: 7., y: 7. }, Tile::KNIGHT_M_IDLE_ANIM_0),
-        (Point { x: 10., y: 7. }, Tile::KNIGHT_F_IDLE_ANIM_0),
-        (Point { x: 13., y: 7. }, Tile::WIZZARD_M_IDLE_ANIM_0),
-        (Point { x: 16., y: 7. }, Tile::WIZZARD_F_IDLE_ANIM_0),
-        (Point { x: 19., y: 7. }, Tile::LIZARD_M_IDLE_ANIM_0),
-        (Point { x: 22., y: 7. }, Tile::LIZARD_F_IDLE_ANIM_0),
-        (Point { x: 1., y: 11.5 }, Tile::IMP_IDLE_ANIM_0),
-        (Point { x: 4., y: 11.5 }, Tile::NECROMANCER_IDLE_ANIM_0),
-        (Point { x: 7., y: 11.5 }, Tile::WOGOL_IDLE_ANIM_0),
-        (Point { x: 10., y: 11.5 }, Tile::CHORT_IDLE_ANIM_0),
-        (Point { x: 13., y: 11.5 }, Tile::TINY_ZOMBIE_IDLE_ANIM_0),
-        (Point { x: 16., y: 11.5 }, Tile::ZOMBIE_IDLE_ANIM_0),
-        (Point { x: 19., y: 11.5 }, Tile::ICE_ZOMBIE_IDLE_ANIM_0),
-        (Point { x: 22., y: 11.5 }, Tile::SKELET_IDLE_ANIM_0),
-        (Point { x: 25., y: 11.5 }, Tile::MASKED_ORC_IDLE_ANIM_0),
-        (Point { x: 28., y: 11.5 }, Tile::ORC_WARRIOR_IDLE_ANIM_0),
-        (Point { x: 31., y: 11.5 }, Tile::ORC_SHAMAN_IDLE_ANIM_0),
-        (Point { x: 34., y: 11.5 }, Tile::GOBLIN_IDLE_ANIM_0),
-        (Point { x: 37., y: 11.5 }, Tile::MUDDY_IDLE_ANIM_0),
-        (Point { x: 40., y: 11.5 }, Tile::SWAMPY_IDLE_ANIM_0),
-        (Point { x: 1., y: 15.5 }, Tile::BIG_DEMON_IDLE_ANIM_0),
-        (Point { x: 7., y: 15.5 }, Tile::BIG_ZOMBIE_IDLE_ANIM_0),
-        (Point { x: 13., y: 15.5 }, Tile::OGRE_IDLE_ANIM_0),
-        (Point { x: 1., y: 21. }, Tile::WEAPON_KNIFE),
-        (Point { x: 4., y: 21. }, Tile::WEAPON_RUSTY_SWORD),
-        (Point { x: 7., y: 21. }, Tile::WEAPON_REGULAR_SWORD),
-        (Point { x: 10., y: 21. }, Tile::WEAPON_RED_GEM_SWORD),
-        (Point { x: 13., y: 21. }, Tile::WEAPON_BIG_HAMMER),
-        (Point { x: 16., y: 21. }, Tile::WEAPON_HAMMER),
-        (Point { x: 19., y: 21. }, Tile::WEAPON_BATON_WITH_SPIKES),
-        (Point { x: 22., y: 21. }, Tile::WEAPON_MACE),
-        (Point { x: 25., y: 21. }, Tile::WEAPON_KATANA),
-        (Point { x: 28., y: 21. }, Tile::WEAPON_SAW_SWORD),
-        (Point { x: 31., y: 21. }, Tile::WEAPON_ANIME_SWORD),
-        (Point { x: 34., y: 21. }, Tile::WEAPON_AXE),
-        (Point { x: 37., y: 21. }, Tile::WEAPON_MACHETE),
-        (Point { x: 40., y: 21. }, Tile::WEAPON_CLEAVER),
-        (Point { x: 43., y: 21. }, Tile::WEAPON_DUEL_SWORD),
-        (Point { x: 46., y: 21. }, Tile::WEAPON_KNIGHT_SWORD),
-        (Point { x: 49., y: 21. }, Tile::WEAPON_GOLDEN_SWORD),
-        (Point { x: 52., y: 21. }, Tile::WEAPON_LAVISH_SWORD),
-        (Point { x: 55., y: 21. }, Tile::WEAPON_RED_MAGIC_STAFF),
-        (Point { x: 58., y: 21. }, Tile::WEAPON_GREEN_MAGIC_STAFF),
-        (Point { x: 61., y: 21. }, Tile::WEAPON_SPEAR),
-        (Point { x: 64., y: 21. }, Tile::WEAPON_ARROW),
-        (Point { x: 67., y: 21. }, Tile::WEAPON_BOW),
-        (Point { x: 1., y: 27. }, Tile::FLASK_BIG_RED),
-        (Point { x: 4., y: 27. }, Tile::FLASK_BIG_BLUE),
-        (Point { x: 7., y: 27. }, Tile::FLASK_BIG_GREEN),
-        (Point { x: 10., y: 27. }, Tile::FLASK_BIG_YELLOW),
-        (Point { x: 13., y: 27. }, Tile::FLASK_RED),
-        (Point { x: 16., y: 27. }, Tile::FLASK_BLUE),
-        (Point { x: 19., y: 27. }, Tile::FLASK_GREEN),
-        (Point { x: 22., y: 27. }, Tile::FLASK_YELLOW),
-        (Point { x: 25., y: 27. }, Tile::COIN_ANIM_0),
-        (Point { x: 28., y: 27. }, Tile::CHEST_EMPTY_OPEN_ANIM_0),
-        (Point { x: 31., y: 27. }, Tile::CHEST_FULL_OPEN_ANIM_0),
-        (Point { x: 34., y: 27. }, Tile::CHEST_MIMIC_OPEN_ANIM_0),
-        (Point { x: 37., y: 27. }, Tile::CRATE),
-        (Point { x: 40., y: 27. }, Tile::SKULL),
-        */
-    ];
+    // The pencil tile, shrunk to a single cell, and its name
+    fn draw_pencil(
+        ctx: &mut Context,
+        tile_renderer: &mut TileRenderer,
+        pencil: Option<&Pencil>,
+        Point { x, y }: Point,
+    ) {
+        let tile = match pencil {
+            Some(&Pencil::Floor((floor, orientation))) =>
+                Some((tile_renderer.tile(floor), orientation)),
+            Some(&Pencil::Wall(wall)) => Some((tile_renderer.tile(wall), North)),
+            Some(Pencil::Prefab(_)) | None => None,
+        };
+
+        let cell = tile_renderer.catalogue().cell();
+        if let Some((tile, orientation)) = tile {
+            let scale = (cell.x / tile.w as f32).min(cell.y / tile.h as f32).min(1.);
+
+            tile_renderer.clear();
+            tile_renderer.add((tile, [0., 0.], orientation));
+            tile_renderer.draw(ctx, [x, y], scale);
+        }
+
+        let name = match pencil {
+            Some(pencil) => pencil.name(),
+            None => String::from("Selection"),
+        };
+        Text::new(format!("Pencil: {}", name))
+            .draw(ctx, DrawParam::new().dest([x + 2. * cell.x, y]))
+            .unwrap();
+    }
+
+    // Kept inside the window
+    fn tooltip(ctx: &mut Context, name: &str, Point { x, y }: Point) {
+        let text = Text::new(name);
+        let Rect { w, h, .. } = text.dimensions(ctx);
+        let (width, _) = drawable_size(ctx);
+        let (x, y) = ((x + 12.).min(width - w - 4.), y + 12.);
+
+        MeshBuilder::new()
+            .rectangle(
+                DrawMode::fill(),
+                [x - 4., y - 4., w + 8., h + 8.].into(),
+                Color::BLACK,
+            )
+            .unwrap()
+            .build(ctx)
+            .unwrap()
+            .draw(ctx, DrawParam::new())
+            .unwrap();
+        text.draw(ctx, DrawParam::new().dest([x, y])).unwrap();
+    }
+
+    // Rows are sorted vertically and their entries horizontally
+    fn entry_at(&self, layout: &Layout, Point { x, y }: Point) -> Option<Entry> {
+        if !self.contains([x, y].into()) || y < self.rect.y + Self::HEADER {
+            return None;
+        }
+
+        let y = y + self.scroll;
+        let row = layout
+            .rows
+            .get(layout.rows.partition_point(|row| row.y + row.h < y))?;
+        let &(entry, rect) = row
+            .entries
+            .get(row.entries.partition_point(|(_, rect)| rect.x + rect.w < x))?;
+
+        if rect.contains([x, y]) {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    // Sections of entries matching the filter, wrapped to the window width and
+    // sized after their tiles
+    fn layout(&self, prefabs: &Prefabs, catalogue: &Catalogue) -> Layout {
+        let filter = self.filter.text().to_lowercase();
+        let matches = |entry: &Entry| entry.name(prefabs).to_lowercase().contains(&filter);
+
+        let mut sections = vec![("Recent", self.recent.clone())];
+        sections.extend(Element::categories().iter().map(|(label, elements)| {
+            (
+                *label,
+                elements.iter().copied().map(Entry::Element).collect(),
+            )
+        }));
+        sections.push(("Prefabs", (0..prefabs.len()).map(Entry::Prefab).collect()));
+
+        let Rect {
+            x: left, y: top, w, ..
+        } = self.rect;
+        let mut layout = Layout::default();
+        let mut y = top + Self::HEADER;
+
+        for (label, entries) in sections {
+            let entries = entries
+                .into_iter()
+                .filter(|entry| matches(entry))
+                .collect::<Vec<_>>();
+            if entries.is_empty() {
+                continue;
+            }
+
+            layout.labels.push((label, [left + Self::MARGIN, y].into()));
+            y += Self::LABEL;
+
+            let mut x = left + Self::MARGIN;
+            let mut row = Row::new(y);
+
+            for entry in entries {
+                let size = entry.size(prefabs, catalogue, self.scale);
+
+                if x + size.x > left + w - Self::MARGIN && !row.entries.is_empty() {
+                    y += row.h + Self::SPACING;
+                    x = left + Self::MARGIN;
+                    layout
+                        .rows
+                        .push(std::mem::replace(&mut row, Row::new(y)).aligned());
+                }
+
+                row.push(entry, Rect::new(x, y, size.x, size.y));
+                x += size.x + Self::SPACING;
+            }
+
+            y += row.h + 2. * Self::SPACING;
+            layout.rows.push(row.aligned());
+        }
+
+        layout.height = y + Self::MARGIN - top;
+        layout
+    }
 }
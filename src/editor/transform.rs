@@ -0,0 +1,102 @@
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Transform {
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    MirrorHorizontal,
+    MirrorVertical,
+}
+
+impl Transform {
+    pub fn from_keyboard(keyboard: &Keyboard) -> Option<Self> {
+        let transforms = [
+            (Action::Rotate90, Self::Rotate90),
+            (Action::Rotate180, Self::Rotate180),
+            (Action::Rotate270, Self::Rotate270),
+            (Action::MirrorHorizontal, Self::MirrorHorizontal),
+            (Action::MirrorVertical, Self::MirrorVertical),
+        ];
+
+        transforms
+            .iter()
+            .find(|(action, _)| keyboard.action(*action))
+            .map(|&(_, transform)| transform)
+    }
+
+    // Maps a point local to a region of `size` into the transformed region
+    pub fn point(&self, Point { x, y }: Point<i16>, size: Point<i16>) -> Point<i16> {
+        let Point { x: w, y: h } = size;
+
+        match self {
+            Self::Rotate90 => Point { x: h - 1 - y, y: x },
+            Self::Rotate180 => Point {
+                x: w - 1 - x,
+                y: h - 1 - y,
+            },
+            Self::Rotate270 => Point { x: y, y: w - 1 - x },
+            Self::MirrorHorizontal => Point { x: w - 1 - x, y },
+            Self::MirrorVertical => Point { x, y: h - 1 - y },
+        }
+    }
+
+    // The transformed region shares the top left corner of the original one
+    pub fn map(
+        &self,
+        Point { x, y }: Point<i16>,
+        (rx, ry): &(Range<i16>, Range<i16>),
+    ) -> Point<i16> {
+        let size = Point {
+            x: rx.end - rx.start,
+            y: ry.end - ry.start,
+        };
+        let Point { x, y } = self.point([x - rx.start, y - ry.start].into(), size);
+
+        Point {
+            x: rx.start + x,
+            y: ry.start + y,
+        }
+    }
+
+    pub fn orientation(&self, mut orientation: Orientation) -> Orientation {
+        match self {
+            Self::Rotate90 => orientation.rotate_right(),
+            Self::Rotate180 => {
+                orientation.rotate_right();
+                orientation.rotate_right();
+            }
+            Self::Rotate270 => orientation.rotate_left(),
+            Self::MirrorHorizontal => orientation.mirror_horizontal(),
+            Self::MirrorVertical => orientation.mirror_vertical(),
+        }
+
+        orientation
+    }
+
+    // Each edge of the cell goes to its transformed edge. Sides have no wall
+    // type, so they become default walls when turning horizontal. The wall on
+    // the top edge is returned apart, being the bottom of the cell above
+    pub fn walls(&self, walls: Walls) -> (Walls, Option<WallEnum>) {
+        let Walls {
+            bottom,
+            left,
+            right,
+        } = walls;
+        let side = |side: bool| {
+            if side {
+                Some(WallEnum::default())
+            } else {
+                None
+            }
+        };
+
+        match self {
+            Self::Rotate90 => (Walls::new(side(right), bottom.is_some(), false), side(left)),
+            Self::Rotate180 => (Walls::new(None, right, left), bottom),
+            Self::Rotate270 => (Walls::new(side(left), false, bottom.is_some()), side(right)),
+            Self::MirrorHorizontal => (Walls::new(bottom, right, left), None),
+            Self::MirrorVertical => (Walls::new(None, left, right), bottom),
+        }
+    }
+}
@@ -1,26 +1,75 @@
 use super::*;
 
+// The center of the view, in unscaled pixels, and the scale
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Bookmark {
+    pub center: Point,
+    pub scale:  f32,
+}
+
+// An eased move from one scale and position to another. Interpolating both
+// linearly keeps the zoom point in place
+#[derive(Copy, Clone, Debug)]
+struct Animation {
+    from:    (f32, Point),
+    to:      (f32, Point),
+    elapsed: Duration,
+}
+
+// `rect` is the scrolled area, of the size of the part of the window at
+// `offset`. Cells are `cell` pixels at scale 1, after the tileset
 #[derive(Copy, Clone, Debug)]
 pub struct Viewport {
-    scale: f32,
-    rect:  Rect,
+    scale:     f32,
+    cell:      Point,
+    rect:      Rect,
+    offset:    Point,
+    snap:      bool,
+    animation: Option<Animation>,
+    velocity:  Point,
 }
 
 impl Viewport {
-    pub fn new(ctx: &mut Context) -> Self {
-        let (w, h) = ggez::graphics::drawable_size(ctx);
+    const MARGIN: f32 = 32.;
 
+    pub fn new(size: Point) -> Self {
         Self {
-            scale: 1.,
-            rect:  [0., 0., w, h].into(),
+            scale:     1.,
+            cell:      Catalogue::default_tileset().cell(),
+            rect:      [0., 0., size.x, size.y].into(),
+            offset:    [0., 0.].into(),
+            snap:      false,
+            animation: None,
+            velocity:  [0., 0.].into(),
         }
     }
 
-    pub fn set_size(&mut self, ctx: &mut Context) {
-        let (w, h) = ggez::graphics::drawable_size(ctx);
+    pub fn set_size(&mut self, size: Point) {
+        self.rect.w = size.x;
+        self.rect.h = size.y;
+    }
+
+    // E.g. for a pane of a split view
+    pub fn set_screen(&mut self, screen: Rect) {
+        self.offset = [screen.x, screen.y].into();
+        self.set_size([screen.w, screen.h].into());
+    }
+
+    pub fn screen(&self) -> Rect {
+        [self.offset.x, self.offset.y, self.rect.w, self.rect.h].into()
+    }
 
-        self.rect.w = w;
-        self.rect.h = h;
+    pub fn contains(&self, position: impl Into<Point>) -> bool {
+        self.screen().contains(position)
+    }
+
+    // On screen
+    pub fn center(&self) -> Point {
+        [
+            self.offset.x + self.rect.w / 2.,
+            self.offset.y + self.rect.h / 2.,
+        ]
+        .into()
     }
 
     pub fn rect(&self) -> Rect {
@@ -52,100 +101,262 @@ impl Viewport {
     }
 
     pub fn origin(&self) -> Point {
-        [-self.rect.x, -self.rect.y].into()
+        [self.offset.x - self.rect.x, self.offset.y - self.rect.y].into()
     }
 
     pub fn scale(&self) -> f32 {
         self.scale
     }
 
+    pub fn cell(&self) -> Point {
+        self.cell
+    }
+
+    pub fn set_cell(&mut self, cell: Point) {
+        self.cell = cell;
+    }
+
     pub fn tile(&self) -> Point {
-        [self.scale() * TILE_WIDTH, self.scale() * TILE_HEIGHT].into()
+        [self.scale() * self.cell.x, self.scale() * self.cell.y].into()
+    }
+
+    pub fn snap(&self) -> bool {
+        self.snap
+    }
+
+    pub fn toggle_snap(&mut self) {
+        self.snap = !self.snap;
+        self.zoom(self.center(), 0.);
+    }
+
+    // Whole scales (or their inverses) keep the art from shimmering. Snaps in
+    // the direction of `up`, with some tolerance for rounding errors. Below 1,
+    // the inverse is rounded the other way
+    fn snapped(scale: f32, up: bool) -> f32 {
+        let snap = |scale: f32, up: bool| {
+            if up {
+                (scale - 0.01).ceil()
+            } else {
+                (scale + 0.01).floor()
+            }
+        };
+
+        if scale >= 1. {
+            snap(scale, up).max(1.)
+        } else {
+            1. / snap(1. / scale, !up).max(1.)
+        }
+    }
+
+    // Where the current animation ends
+    fn target(&self) -> (f32, Point) {
+        self.animation
+            .map(|animation| animation.to)
+            .unwrap_or((self.scale, self.point()))
+    }
+
+    fn animate_to(&mut self, scale: f32, position: Point) {
+        self.animation = Some(Animation {
+            from:    (self.scale, self.point()),
+            to:      (scale, position),
+            elapsed: Duration::from_secs(0),
+        });
+    }
+
+    // Moves along the animation and the scrolling inertia
+    pub fn animate(&mut self, delta: Duration) {
+        if let Some(mut animation) = self.animation {
+            animation.elapsed += delta;
+
+            let t = (animation.elapsed.as_secs_f32() / CAMERA_DURATION.as_secs_f32()).min(1.);
+            let t = 1. - (1. - t).powi(3);
+            let ((from_scale, from), (to_scale, to)) = (animation.from, animation.to);
+
+            // Ending exactly on the target, e.g. a snapped scale
+            let lerp = |from: f32, to: f32| if t < 1. { from + (to - from) * t } else { to };
+
+            self.scale = lerp(from_scale, to_scale);
+            self.rect.x = lerp(from.x, to.x);
+            self.rect.y = lerp(from.y, to.y);
+            self.animation = Some(animation).filter(|_| t < 1.);
+        }
+
+        // The exact distance travelled while slowing down during `delta`
+        let decay = (-SCROLL_FRICTION * delta.as_secs_f32()).exp();
+        let distance = (1. - decay) / SCROLL_FRICTION;
+        self.translate([self.velocity.x * distance, self.velocity.y * distance]);
+        self.velocity.x *= decay;
+        self.velocity.y *= decay;
+        if self.velocity.x.abs() < 1. && self.velocity.y.abs() < 1. {
+            self.velocity = [0., 0.].into();
+        }
+    }
+
+    // Glides over `scroll`, and further with repeated scrolls
+    pub fn scroll(&mut self, scroll: impl Into<Point>) {
+        let scroll = scroll.into();
+
+        self.velocity.x += scroll.x * SCROLL_FRICTION;
+        self.velocity.y += scroll.y * SCROLL_FRICTION;
     }
 
+    // Keeps what is under `point` there. Zooms add up while animating
     pub fn zoom(&mut self, point: impl Into<Point>, delta: f32) {
         let point = point.into();
-        let scale = self.scale;
-        let delta = if scale + delta > SCALE_LIMIT {
-            SCALE_LIMIT - scale
-        } else if scale + delta < 1. / SCALE_LIMIT {
-            1. / SCALE_LIMIT - scale
+        let point = Point {
+            x: point.x - self.offset.x,
+            y: point.y - self.offset.y,
+        };
+        let (scale, position) = self.target();
+        let target = (scale + delta).max(1. / SCALE_LIMIT).min(SCALE_LIMIT);
+        let target = if self.snap {
+            Self::snapped(target, delta > 0.)
         } else {
-            delta
+            target
         };
+        let ratio = target / scale;
 
-        self.scale += delta;
-        self.translate(Point {
-            x: (delta / scale) * (point.x - self.origin().x),
-            y: (delta / scale) * (point.y - self.origin().y),
+        self.animate_to(target, Point {
+            x: (position.x + point.x) * ratio - point.x,
+            y: (position.y + point.y) * ratio - point.y,
         });
     }
 
     pub fn zoom_reset(&mut self) {
-        self.zoom([self.size().x / 2., self.size().y / 2.], 1. - self.scale);
+        let scale = self.target().0;
+        self.zoom(self.center(), 1. - scale);
     }
 
     pub fn zoom_in(&mut self) {
-        self.zoom([self.size().x / 2., self.size().y / 2.], self.scale);
+        let scale = self.target().0;
+        self.zoom(self.center(), scale);
     }
 
     pub fn zoom_out(&mut self) {
-        self.zoom([self.size().x / 2., self.size().y / 2.], -self.scale / 2.);
+        let scale = self.target().0;
+        self.zoom(self.center(), -scale / 2.);
+    }
+
+    pub fn bookmark(&self) -> Bookmark {
+        Bookmark {
+            center: [
+                (self.rect.x + self.rect.w / 2.) / self.scale,
+                (self.rect.y + self.rect.h / 2.) / self.scale,
+            ]
+            .into(),
+            scale:  self.scale,
+        }
     }
 
+    pub fn restore(&mut self, Bookmark { center, scale }: Bookmark) {
+        self.animate_to(scale, Point {
+            x: center.x * scale - self.rect.w / 2.,
+            y: center.y * scale - self.rect.h / 2.,
+        });
+    }
+
+    // At the current (or upcoming) scale
+    pub fn center_on(&mut self, center: Point) {
+        let scale = self.target().0;
+        self.restore(Bookmark { center, scale });
+    }
+
+    // Fills `area` of the window with the cells of `bounds`, with a margin
+    pub fn fit(&mut self, (x, y): (Range<i16>, Range<i16>), area: Rect) {
+        let w = x.len() as f32 * self.cell.x;
+        let h = y.len() as f32 * self.cell.y;
+        let scale = ((area.w - 2. * Self::MARGIN) / w)
+            .min((area.h - 2. * Self::MARGIN) / h)
+            .max(1. / SCALE_LIMIT)
+            .min(SCALE_LIMIT);
+
+        let scale = if self.snap {
+            Self::snapped(scale, false)
+        } else {
+            scale
+        };
+
+        let center = Point {
+            x: (x.start + x.end) as f32 / 2. * self.cell.x,
+            y: (y.start + y.end) as f32 / 2. * self.cell.y,
+        };
+        self.animate_to(scale, Point {
+            x: center.x * scale - (area.x - self.offset.x + area.w / 2.),
+            y: center.y * scale - (area.y - self.offset.y + area.h / 2.),
+        });
+    }
+
+    // Immediately, the animation following along
     pub fn translate(&mut self, translate: impl Into<Point>) {
         let translate = translate.into();
 
         self.rect.x += translate.x;
         self.rect.y += translate.y;
+        if let Some(animation) = &mut self.animation {
+            for (_, position) in [&mut animation.from, &mut animation.to].iter_mut() {
+                position.x += translate.x;
+                position.y += translate.y;
+            }
+        }
+    }
+
+    pub fn pan(&mut self, pan: impl Into<Point>) {
+        let (scale, position) = self.target();
+        let pan = pan.into();
+
+        self.animate_to(scale, [position.x + pan.x, position.y + pan.y].into());
     }
 
     pub fn handle_keys(&mut self, keyboard: &Keyboard) {
-        let ctrl = keyboard.ctrl();
-        let plus = keyboard.is_pressed(KeyCode::Plus);
-        let minus = keyboard.is_pressed(KeyCode::Minus);
-        let page_up = keyboard.is_pressed(KeyCode::PageUp);
-        let page_down = keyboard.is_pressed(KeyCode::PageDown);
-        let up = keyboard.is_pressed(KeyCode::Up);
-        let down = keyboard.is_pressed(KeyCode::Down);
-        let left = keyboard.is_pressed(KeyCode::Left);
-        let right = keyboard.is_pressed(KeyCode::Right);
-        let zero = keyboard.is_pressed(KeyCode::Key0);
-        let equals = keyboard.is_pressed(KeyCode::Equals);
-
-        if zero {
-            self.rect.x = 0.;
-            self.rect.y = 0.;
+        if keyboard.action(Action::Origin) {
+            self.animate_to(self.target().0, [0., 0.].into());
+        }
+
+        if keyboard.action(Action::ToggleSnap) {
+            self.toggle_snap();
         }
 
-        if equals {
+        if keyboard.action(Action::ZoomReset) {
             self.zoom_reset();
-        } else if plus || page_up {
+        } else if keyboard.action(Action::ZoomIn) {
             self.zoom_in();
-        } else if minus || page_down {
+        } else if keyboard.action(Action::ZoomOut) {
             self.zoom_out();
         }
 
-        let steps = if ctrl { 1. } else { 10. };
-        if up {
-            self.rect.y -= self.rect.h / steps;
-        } else if down {
-            self.rect.y += self.rect.h / steps;
-        } else if left {
-            self.rect.x -= self.rect.w / steps;
-        } else if right {
-            self.rect.x += self.rect.w / steps;
+        let (w, h) = (self.rect.w, self.rect.h);
+        let pans = [
+            (Action::PanUp, [0., -h / 10.]),
+            (Action::PanDown, [0., h / 10.]),
+            (Action::PanLeft, [-w / 10., 0.]),
+            (Action::PanRight, [w / 10., 0.]),
+            (Action::PanScreenUp, [0., -h]),
+            (Action::PanScreenDown, [0., h]),
+            (Action::PanScreenLeft, [-w, 0.]),
+            (Action::PanScreenRight, [w, 0.]),
+        ];
+
+        if let Some(&(_, pan)) = pans.iter().find(|(action, _)| keyboard.action(*action)) {
+            self.pan(pan);
+        }
+    }
+
+    // Dragging with the middle button moves the scene along
+    pub fn handle_mouse(&mut self, mouse: &Mouse) {
+        if mouse.middle_drag().is_some() {
+            let motion = mouse.motion();
+            self.translate([-motion.x, -motion.y]);
         }
     }
 
     pub fn coordinates(&self, position: impl Into<Point>) -> Point {
         let position = position.into();
         let origin = self.origin();
-        let scale = self.scale();
+        let tile = self.tile();
 
         [
-            (position.x - origin.x) / (scale * TILE_WIDTH),
-            (position.y - origin.y) / (scale * TILE_HEIGHT),
+            (position.x - origin.x) / tile.x,
+            (position.y - origin.y) / tile.y,
         ]
         .into()
     }
@@ -0,0 +1,62 @@
+use super::*;
+
+// Notices when files change on disk, polling their modification times and
+// sizes every `interval` (the times alone can miss quick successive writes)
+#[derive(Clone, Debug)]
+pub struct Watch {
+    paths:    Vec<PathBuf>,
+    files:    Vec<Option<(SystemTime, u64)>>,
+    interval: Duration,
+    checked:  Instant,
+}
+
+impl Watch {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self::with_interval(paths, WATCH_INTERVAL)
+    }
+
+    pub fn with_interval(paths: Vec<PathBuf>, interval: Duration) -> Self {
+        Self {
+            files: Self::files(&paths),
+            paths,
+            interval,
+            checked: Instant::now(),
+        }
+    }
+
+    // Watches other files as often
+    pub fn set_paths(&mut self, paths: Vec<PathBuf>) {
+        *self = Self::with_interval(paths, self.interval);
+    }
+
+    // Missing files are `None`
+    fn files(paths: &[PathBuf]) -> Vec<Option<(SystemTime, u64)>> {
+        paths
+            .iter()
+            .map(|path| {
+                std::fs::metadata(path)
+                    .and_then(|data| Ok((data.modified()?, data.len())))
+                    .ok()
+            })
+            .collect()
+    }
+
+    // Since the previous call
+    pub fn changed(&mut self) -> bool {
+        if self.checked.elapsed() < self.interval {
+            return false;
+        }
+
+        let files = Self::files(&self.paths);
+        let changed = files != self.files;
+        self.files = files;
+        self.checked = Instant::now();
+
+        changed
+    }
+
+    // E.g. after writing the files ourselves
+    pub fn forget(&mut self) {
+        self.files = Self::files(&self.paths);
+    }
+}
@@ -1,96 +1,254 @@
 use super::*;
 
-pub struct Grid;
+// Lines between cells, thicker ones every `major` cells (e.g. rooms), and
+// `minor` subdivisions of the cells once zoomed in enough. The meshes only
+// depend on the scale and the size of the viewport, and are kept for the last
+// two of them (one per pane)
+#[derive(Clone, Debug)]
+pub struct Grid {
+    major:       u16,
+    minor:       u16,
+    ruler:       bool,
+    major_color: Color,
+    cell_color:  Color,
+    minor_color: Color,
+    cache:       Vec<(f32, Point, Mesh)>,
+}
 
 impl Grid {
-    pub fn draw(ctx: &mut Context, viewport: Viewport) {
-        let mut grid = MeshBuilder::new();
-        let mut base = MeshBuilder::new();
-
-        Self::grid(&mut grid, viewport);
-        Self::base(&mut base, viewport);
-
-        let dest = |origin, tile| {
-            if origin > 0. {
-                (origin % tile) - tile
-            } else if origin < 0. {
-                origin % tile
-            } else {
-                0.
+    const LABEL_SPACING: f32 = 40.;
+    const RULER: f32 = 18.;
+
+    pub fn new() -> Self {
+        Self {
+            major:       16,
+            minor:       16,
+            ruler:       true,
+            major_color: Color::new(0., 1., 0., 0.9),
+            cell_color:  Color::new(0., 1., 0., 0.5),
+            minor_color: Color::new(0., 0.5, 0., 0.25),
+            cache:       Vec::new(),
+        }
+    }
+
+    // Defaults, overridden by the grid file, if any
+    pub fn load() -> Self {
+        let mut grid = Self::new();
+
+        if let Ok(text) = std::fs::read_to_string(GRID) {
+            grid.read(&text);
+        }
+
+        grid
+    }
+
+    // E.g. `major 16`, `minor 4`, `ruler off` or `color minor 0 0.5 0 0.25`
+    fn read(&mut self, text: &str) {
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            let color = |words: &[&str]| {
+                let rgba = words
+                    .iter()
+                    .map(|word| word.parse())
+                    .collect::<Result<Vec<f32>, _>>();
+
+                match rgba.as_deref() {
+                    Ok(&[r, g, b, a]) => Some(Color::new(r, g, b, a)),
+                    _ => None,
+                }
+            };
+
+            let count = |n: &str| n.parse().ok().filter(|&n| n > 0);
+            let parsed = match words.as_slice() {
+                [] => continue,
+                ["major", n] => count(n).map(|n| self.major = n),
+                ["minor", n] => count(n).map(|n| self.minor = n),
+                ["ruler", on] => match *on {
+                    "on" => Some(true),
+                    "off" => Some(false),
+                    _ => None,
+                }
+                .map(|on| self.ruler = on),
+                ["color", "major", rgba @ ..] => color(rgba).map(|c| self.major_color = c),
+                ["color", "cell", rgba @ ..] => color(rgba).map(|c| self.cell_color = c),
+                ["color", "minor", rgba @ ..] => color(rgba).map(|c| self.minor_color = c),
+                _ => None,
+            };
+
+            if parsed.is_none() {
+                println!("{}:{}: invalid line {}", GRID, i + 1, line.trim());
+            }
+        }
+    }
+
+    pub fn draw(&mut self, ctx: &mut Context, viewport: Viewport) {
+        let tile = viewport.tile();
+        let period = Point {
+            x: tile.x * self.major as f32,
+            y: tile.y * self.major as f32,
+        };
+        let key = (viewport.scale(), viewport.size());
+
+        let i = match self.cache.iter().position(|(s, z, _)| (*s, *z) == key) {
+            Some(i) => i,
+            None => {
+                let mesh = self.mesh(ctx, viewport, period);
+                self.cache.push((key.0, key.1, mesh));
+                if self.cache.len() > 2 {
+                    self.cache.remove(0);
+                }
+                self.cache.len() - 1
             }
         };
 
-        let grid = grid.build(ctx).unwrap();
-        grid.draw(
-            ctx,
-            DrawParam::new().dest([
-                dest(viewport.origin().x, viewport.tile().x),
-                dest(viewport.origin().y, viewport.tile().y),
-            ]),
-        )
-        .unwrap();
-
-        let base = base.build(ctx).unwrap();
-        base.draw(ctx, DrawParam::new().dest(viewport.origin()))
+        // The mesh spans a period more than the viewport, so that it can be
+        // moved back to the previous major line
+        let screen = viewport.screen();
+        let origin = viewport.origin();
+        let dest = |origin: f32, offset: f32, period: f32| {
+            offset + (origin - offset).rem_euclid(period) - period
+        };
+        self.cache[i]
+            .2
+            .draw(
+                ctx,
+                DrawParam::new().dest([
+                    dest(origin.x, screen.x, period.x),
+                    dest(origin.y, screen.y, period.y),
+                ]),
+            )
             .unwrap();
-    }
 
-    fn grid(mesh: &mut MeshBuilder, viewport: Viewport) {
-        let green = Color::new(0., 1., 0., 0.5);
-        let red = Color::new(
-            0.,
-            0.5,
-            0.,
-            if viewport.scale() >= SCALE_LIMIT / 2. {
-                0.25
-            } else {
-                0.
-            },
-        );
+        Self::base(ctx, viewport);
 
-        let w = viewport.tile().x;
-        let h = viewport.tile().y;
-        let width = viewport.w() + w;
-        let height = viewport.h() + h;
+        if self.ruler {
+            self.draw_ruler(ctx, viewport);
+        }
+    }
 
-        // Vertical lines
-        let mut x = 1.;
-        while x < width {
-            Self::vertical(mesh, x, height, green);
+    fn mesh(&self, ctx: &mut Context, viewport: Viewport, period: Point) -> Mesh {
+        let mut mesh = MeshBuilder::new();
+        let tile = viewport.tile();
+        let width = viewport.w() + period.x;
+        let height = viewport.h() + period.y;
+        // Subdivisions are too close to be seen when zoomed out
+        let minor = viewport.scale() >= SCALE_LIMIT / 2. && self.minor > 1;
+
+        let mut x = 0;
+        while x as f32 * tile.x < width {
+            let left = x as f32 * tile.x + 1.;
+            if x % self.major == 0 {
+                mesh.line(&[[left, 0.], [left, height]], 2., self.major_color)
+                    .unwrap();
+            } else {
+                Self::vertical(&mut mesh, left, height, self.cell_color);
+            }
 
-            for i in 1..16 {
-                let i = i as f32 * w / 16.;
-                Self::vertical(mesh, x + i, height, red);
+            for i in (1..self.minor).filter(|_| minor) {
+                let i = i as f32 * tile.x / self.minor as f32;
+                Self::vertical(&mut mesh, left + i, height, self.minor_color);
             }
 
-            x += w;
+            x += 1;
         }
 
-        // Horizontal lines
-        let mut y = 1.;
-        while y < height {
-            Self::horizontal(mesh, y, width, green);
+        let mut y = 0;
+        while y as f32 * tile.y < height {
+            let top = y as f32 * tile.y + 1.;
+            if y % self.major == 0 {
+                mesh.line(&[[0., top], [width, top]], 2., self.major_color)
+                    .unwrap();
+            } else {
+                Self::horizontal(&mut mesh, top, width, self.cell_color);
+            }
 
-            for i in 1..16 {
-                let i = i as f32 * h / 16.;
-                Self::horizontal(mesh, y + i, width, red);
+            for i in (1..self.minor).filter(|_| minor) {
+                let i = i as f32 * tile.y / self.minor as f32;
+                Self::horizontal(&mut mesh, top + i, width, self.minor_color);
             }
 
-            y += h;
+            y += 1;
         }
+
+        mesh.build(ctx).unwrap()
     }
 
-    fn base(mesh: &mut MeshBuilder, viewport: Viewport) {
+    fn base(ctx: &mut Context, viewport: Viewport) {
+        let mut mesh = MeshBuilder::new();
         let blue = Color::new(0., 0., 1., 1.);
         let w = viewport.tile().x;
         let h = viewport.tile().y;
 
         // Origin
-        Self::circle(mesh, (w + h) / 8., blue);
+        Self::circle(&mut mesh, (w + h) / 8., blue);
 
         // Base
-        Self::horizontal(mesh, 1., w, blue);
-        Self::vertical(mesh, 1., h, blue);
+        Self::horizontal(&mut mesh, 1., w, blue);
+        Self::vertical(&mut mesh, 1., h, blue);
+
+        mesh.build(ctx)
+            .unwrap()
+            .draw(ctx, DrawParam::new().dest(viewport.origin()))
+            .unwrap();
+    }
+
+    // Cell coordinates along the top and left edges, on major lines unless
+    // cells are large enough to each have theirs
+    fn draw_ruler(&self, ctx: &mut Context, viewport: Viewport) {
+        let screen = viewport.screen();
+        let origin = viewport.origin();
+        let tile = viewport.tile();
+        let major = self.major as i32;
+        let step = |tile: f32| {
+            if tile >= Self::LABEL_SPACING {
+                1
+            } else {
+                let period = major as f32 * tile;
+                major * (Self::LABEL_SPACING / period).ceil() as i32
+            }
+        };
+
+        MeshBuilder::new()
+            .rectangle(
+                DrawMode::fill(),
+                [screen.x, screen.y, screen.w, Self::RULER].into(),
+                Color::new(0., 0., 0., 0.6),
+            )
+            .unwrap()
+            .rectangle(
+                DrawMode::fill(),
+                [screen.x, screen.y + Self::RULER, Self::RULER * 2., screen.h].into(),
+                Color::new(0., 0., 0., 0.6),
+            )
+            .unwrap()
+            .build(ctx)
+            .unwrap()
+            .draw(ctx, DrawParam::new())
+            .unwrap();
+
+        // The first labelled cells, at or after the edges
+        let labels = |origin: f32, start: f32, end: f32, tile: f32| {
+            let step = step(tile);
+            let first = ((start - origin) / tile).ceil() as i32;
+            let first = first + (step - first.rem_euclid(step)) % step;
+
+            (first..)
+                .step_by(step as usize)
+                .map(move |cell| (cell, origin + cell as f32 * tile))
+                .take_while(move |(_, position)| *position < end)
+        };
+
+        for (x, position) in labels(origin.x, screen.x, screen.right(), tile.x) {
+            Text::new(x.to_string())
+                .draw(ctx, DrawParam::new().dest([position + 3., screen.y + 1.]))
+                .unwrap();
+        }
+        for (y, position) in labels(origin.y, screen.y + Self::RULER, screen.bottom(), tile.y) {
+            Text::new(y.to_string())
+                .draw(ctx, DrawParam::new().dest([screen.x + 2., position + 1.]))
+                .unwrap();
+        }
     }
 
     fn vertical(mesh: &mut MeshBuilder, x: f32, h: f32, color: Color) {
@@ -8,28 +8,30 @@ pub use grid::*;
 pub use tile::*;
 pub use tile_renderer::*;
 
+// The source rect is set by the tile renderer, from the size of the atlas.
+// Positions are in cells of the tileset, of `cell` pixels
 pub trait Params {
-    fn params(self) -> DrawParam;
+    fn params(self, cell: Point) -> (Tile, DrawParam);
 }
 
 impl<T: Into<Point>> Params for (Tile, T) {
-    fn params(self) -> DrawParam {
+    fn params(self, cell: Point) -> (Tile, DrawParam) {
         let Point { x, y } = self.1.into();
 
-        DrawParam::new()
-            .src(self.0.rect())
-            .dest([x * TILE_WIDTH, y * TILE_HEIGHT])
+        (self.0, DrawParam::new().dest([x * cell.x, y * cell.y]))
     }
 }
 
 impl<T: Into<Point>> Params for (Tile, T, f32) {
-    fn params(self) -> DrawParam {
-        (self.0, self.1).params().scale([self.2, self.2])
+    fn params(self, cell: Point) -> (Tile, DrawParam) {
+        let (tile, params) = (self.0, self.1).params(cell);
+
+        (tile, params.scale([self.2, self.2]))
     }
 }
 
 impl<T: Into<Point>> Params for (Tile, T, Orientation) {
-    fn params(self) -> DrawParam {
+    fn params(self, cell: Point) -> (Tile, DrawParam) {
         let Point { x, y } = self.1.into();
 
         let (x, y, rotation) = match self.2 {
@@ -39,6 +41,8 @@ impl<T: Into<Point>> Params for (Tile, T, Orientation) {
             West => (x, y + 1., -TAU / 4.),
         };
 
-        (self.0, [x, y]).params().rotation(rotation)
+        let (tile, params) = (self.0, [x, y]).params(cell);
+
+        (tile, params.rotation(rotation))
     }
 }
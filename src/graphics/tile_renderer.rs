@@ -1,31 +1,199 @@
 use super::*;
 
+// An image of the tileset, tiles being tagged with its index
 #[derive(Clone, Debug)]
-pub struct TileRenderer {
+struct Atlas {
     batch: SpriteBatch,
+    size:  Point,
+}
+
+impl Atlas {
+    fn load(ctx: &mut Context, path: &str) -> GameResult<Self> {
+        let mut image = Image::new(ctx, path)?;
+        image.set_filter(FilterMode::Nearest);
+        let size = [image.width() as f32, image.height() as f32].into();
+
+        Ok(Self {
+            batch: SpriteBatch::new(image),
+            size,
+        })
+    }
+}
+
+// The atlases of the `name` tileset are `/name.png`, then `/name_1.png`,
+// `/name_2.png` and so on, its tiles being described in `/name.txt`.
+// `requested` is the last tileset asked for, loaded or not. The catalogue is
+// shared with the editor, which lays the palette out after it
+#[derive(Clone, Debug)]
+pub struct TileRenderer {
+    atlases:   Vec<Atlas>,
+    catalogue: Rc<Catalogue>,
+    tileset:   String,
+    requested: String,
+    color:     Color,
+    watch:     Watch,
 }
 
 impl TileRenderer {
     pub fn new(ctx: &mut Context) -> Self {
-        let mut image = Image::new(ctx, ARTPACK).expect("Cannot read artpack");
-        image.set_filter(FilterMode::Nearest);
+        let (atlases, catalogue, problems) = Self::load(ctx, TILESET).expect("Cannot read artpack");
+        for problem in problems {
+            println!("{}", problem);
+        }
 
         Self {
-            batch: SpriteBatch::new(image),
+            watch: Self::watch(ctx, TILESET, atlases.len()),
+            atlases,
+            catalogue: Rc::new(catalogue),
+            tileset: String::from(TILESET),
+            requested: String::from(TILESET),
+            color: Color::WHITE,
+        }
+    }
+
+    // The images and the description
+    fn watch(ctx: &Context, tileset: &str, atlases: usize) -> Watch {
+        let dir = ggez::filesystem::resources_dir(ctx);
+        let mut paths = vec![dir.join(format!("{}.png", tileset))];
+        paths.extend((1..atlases).map(|i| dir.join(format!("{}_{}.png", tileset, i))));
+        paths.push(dir.join(format!("{}.txt", tileset)));
+
+        Watch::new(paths)
+    }
+
+    // When its files change, with a message for the status bar, rather than
+    // the console, that also lists the problems. Images being written fail to
+    // load, the previous tiles being kept until the next change
+    pub fn reload(&mut self, ctx: &mut Context) -> Option<String> {
+        if !self.watch.changed() {
+            return None;
+        }
+
+        match Self::load(ctx, &self.tileset) {
+            Ok((atlases, catalogue, problems)) => {
+                self.watch = Self::watch(ctx, &self.tileset, atlases.len());
+                self.atlases = atlases;
+                self.catalogue = Rc::new(catalogue);
+
+                let mut message = vec![format!("Reloaded tileset {}", self.tileset)];
+                message.extend(problems);
+                Some(message.join(". "))
+            }
+            Err(err) => Some(format!("Cannot reload tileset {}: {}", self.tileset, err)),
+        }
+    }
+
+    // The tiles are only replaced along with the atlases, once both loaded.
+    // Problems are reported by the callers
+    fn load(ctx: &mut Context, tileset: &str) -> GameResult<(Vec<Atlas>, Catalogue, Vec<String>)> {
+        let mut problems = Vec::new();
+        let path = format!("/{}.txt", tileset);
+        let catalogue = Self::read_catalogue(ctx, &path).unwrap_or_else(|| {
+            if tileset != TILESET {
+                problems.push(format!("Cannot read {}, using the default tiles", path));
+            }
+            Catalogue::default_tileset()
+        });
+        let mut atlases = vec![Atlas::load(ctx, &format!("/{}.png", tileset))?];
+
+        loop {
+            let path = format!("/{}_{}.png", tileset, atlases.len());
+            if !ggez::filesystem::exists(ctx, &path) {
+                break;
+            }
+            atlases.push(Atlas::load(ctx, &path)?);
+        }
+
+        // Their tiles are skipped when drawing
+        if catalogue.atlases() > atlases.len() {
+            problems.push(format!(
+                "Tileset {} has {} atlases, its tiles use {}",
+                tileset,
+                atlases.len(),
+                catalogue.atlases()
+            ));
+        }
+        if !catalogue.missing().is_empty() {
+            problems.push(format!(
+                "Tileset {} lacks {}",
+                tileset,
+                catalogue.missing().join(" ")
+            ));
+        }
+
+        Ok((atlases, catalogue, problems))
+    }
+
+    // None when unreadable, the built in description serving instead
+    fn read_catalogue(ctx: &mut Context, path: &str) -> Option<Catalogue> {
+        let mut text = String::new();
+
+        match open(ctx, path).map(|mut file| file.read_to_string(&mut text)) {
+            Ok(Ok(_)) => Some(Catalogue::parse(&text, path)),
+            _ => None,
+        }
+    }
+
+    pub fn catalogue(&self) -> &Rc<Catalogue> {
+        &self.catalogue
+    }
+
+    pub fn tile(&self, sprite: impl Sprite) -> Tile {
+        self.catalogue.tile(sprite)
+    }
+
+    pub fn tileset(&self) -> &str {
+        &self.tileset
+    }
+
+    // Keeps the current tileset when `tileset` cannot be loaded, without
+    // trying again until another one is requested
+    pub fn set_tileset(&mut self, ctx: &mut Context, tileset: &str) {
+        if tileset == self.requested {
+            return;
+        }
+        self.requested = tileset.to_string();
+
+        match Self::load(ctx, tileset) {
+            Ok((atlases, catalogue, problems)) => {
+                self.watch = Self::watch(ctx, tileset, atlases.len());
+                self.atlases = atlases;
+                self.catalogue = Rc::new(catalogue);
+                self.tileset = tileset.to_string();
+                for problem in problems {
+                    println!("{}", problem);
+                }
+            }
+            Err(err) => println!("Cannot read tileset {}: {}", tileset, err),
         }
     }
 
     pub fn add(&mut self, params: impl Params) {
-        self.batch.add(params.params());
+        let (tile, params) = params.params(self.catalogue.cell());
+
+        // Missing atlases are reported when loading
+        if let Some(atlas) = self.atlases.get_mut(tile.atlas as usize) {
+            atlas
+                .batch
+                .add(params.src(tile.rect(atlas.size)).color(self.color));
+        }
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
     }
 
     pub fn draw(&mut self, ctx: &mut Context, origin: impl Into<Point>, scale: f32) {
         let param = DrawParam::new().dest(origin).scale([scale, scale]);
 
-        self.batch.draw(ctx, param).unwrap();
+        for atlas in &mut self.atlases {
+            atlas.batch.draw(ctx, param).unwrap();
+        }
     }
 
     pub fn clear(&mut self) {
-        self.batch.clear();
+        for atlas in &mut self.atlases {
+            atlas.batch.clear();
+        }
     }
 }
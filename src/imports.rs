@@ -7,6 +7,7 @@ pub use ggez::{
         drawable_size,
         mint::Point2,
         present,
+        set_scissor_rect,
         spritebatch::SpriteBatch,
         Color,
         DrawMode,
@@ -17,6 +18,7 @@ pub use ggez::{
         Mesh,
         MeshBuilder,
         Rect,
+        Text,
     },
     input::{
         gamepad::{gilrs::ev::EventType, GamepadId},
@@ -29,10 +31,13 @@ pub use ggez::{
 };
 pub use rand::prelude::*;
 pub use std::{
+    cell::RefCell,
     collections::{hash_map::HashMap, hash_set::HashSet},
     f32::consts::TAU,
-    io::Read,
+    io::{Read, Write},
+    iter::FromIterator,
     ops::{Add, Range},
-    path::Path,
-    time::{Duration, Instant},
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::{Duration, Instant, SystemTime},
 };
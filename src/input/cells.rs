@@ -0,0 +1,126 @@
+use super::*;
+
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Cells {
+    cells: HashSet<Point<i16>>,
+}
+
+impl Cells {
+    pub fn new() -> Self {
+        Self {
+            cells: HashSet::default(),
+        }
+    }
+
+    pub fn from_ranges((x, y): (Range<i16>, Range<i16>)) -> Self {
+        let mut cells = Self::new();
+
+        for i in x {
+            for j in y.clone() {
+                cells.cells.insert([i, j].into());
+            }
+        }
+
+        cells
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn contains(&self, cell: &Point<i16>) -> bool {
+        self.cells.contains(cell)
+    }
+
+    pub fn insert(&mut self, cell: Point<i16>) {
+        self.cells.insert(cell);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Point<i16>> + '_ {
+        self.cells.iter().copied()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.cells.union(&other.cells).copied().collect()
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.cells.difference(&other.cells).copied().collect()
+    }
+
+    pub fn bounds(&self) -> Option<(Range<i16>, Range<i16>)> {
+        let sx = self.iter().map(|cell| cell.x).min()?;
+        let ex = self.iter().map(|cell| cell.x).max()?;
+        let sy = self.iter().map(|cell| cell.y).min()?;
+        let ey = self.iter().map(|cell| cell.y).max()?;
+
+        Some((sx..ex + 1, sy..ey + 1))
+    }
+
+    pub fn translate(&self, delta: impl Into<Point<i16>>) -> Self {
+        let delta = delta.into();
+
+        self.iter()
+            .map(|Point { x, y }| Point {
+                x: x + delta.x,
+                y: y + delta.y,
+            })
+            .collect()
+    }
+
+    pub fn transformed(&self, transform: Transform) -> Self {
+        match self.bounds() {
+            Some(bounds) => self
+                .iter()
+                .map(|cell| transform.map(cell, &bounds))
+                .collect(),
+            None => self.clone(),
+        }
+    }
+
+    pub fn draw(&self, ctx: &mut Context, viewport: Viewport) {
+        let Point { x: ox, y: oy } = viewport.origin();
+        let Point { x: tx, y: ty } = viewport.tile();
+        let blue = Color::new(0., 0., 1., 1.);
+        let mut mesh = MeshBuilder::new();
+        let mut empty = true;
+
+        // Only edges between a selected and an unselected cell are drawn
+        for cell in self.iter() {
+            let x = ox + cell.x as f32 * tx + 1.;
+            let y = oy + cell.y as f32 * ty + 1.;
+            let edges = [
+                (top(cell), [[x, y], [x + tx, y]]),
+                (bottom(cell), [[x, y + ty], [x + tx, y + ty]]),
+                (left(cell), [[x, y], [x, y + ty]]),
+                (right(cell), [[x + tx, y], [x + tx, y + ty]]),
+            ];
+
+            for (neighbour, line) in edges {
+                if !self.contains(&neighbour) {
+                    mesh.line(&line, 1., blue).unwrap();
+                    empty = false;
+                }
+            }
+        }
+
+        if !empty {
+            mesh.build(ctx)
+                .unwrap()
+                .draw(ctx, DrawParam::new())
+                .unwrap();
+        }
+    }
+}
+
+impl FromIterator<Point<i16>> for Cells {
+    fn from_iter<I: IntoIterator<Item = Point<i16>>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
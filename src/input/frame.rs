@@ -0,0 +1,127 @@
+use super::*;
+
+// The raw inputs of one frame, from which the keyboard and mouse update
+#[derive(Clone, PartialEq, Debug)]
+pub struct Frame {
+    pub time:     Duration,
+    pub size:     Point,
+    pub keys:     Vec<KeyCode>,
+    pub mods:     KeyMods,
+    pub chars:    Vec<char>,
+    pub position: Point,
+    pub left:     bool,
+    pub middle:   bool,
+    pub right:    bool,
+    pub wheel:    Vec<Point>,
+    pub gamepad:  Vec<GamepadEvent>,
+}
+
+impl Frame {
+    // Characters, wheel moves and gamepad events are received between
+    // updates. Only keys that can be bound are kept, modifiers being in `mods`
+    pub fn live(
+        ctx: &mut Context,
+        time: Duration,
+        chars: Vec<char>,
+        wheel: Vec<Point>,
+        gamepad: Vec<GamepadEvent>,
+    ) -> Self {
+        let (w, h) = drawable_size(ctx);
+        let mut keys = ggez::input::keyboard::pressed_keys(ctx)
+            .iter()
+            .copied()
+            .filter(|&key| key_name(key).is_some())
+            .collect::<Vec<_>>();
+        keys.sort();
+
+        Self {
+            time,
+            size: [w, h].into(),
+            keys,
+            mods: ggez::input::keyboard::active_mods(ctx),
+            chars,
+            position: position(ctx),
+            left: ggez::input::mouse::button_pressed(ctx, MouseButton::Left),
+            middle: ggez::input::mouse::button_pressed(ctx, MouseButton::Middle),
+            right: ggez::input::mouse::button_pressed(ctx, MouseButton::Right),
+            wheel,
+            gamepad,
+        }
+    }
+
+    // `millis w h x y buttons mods keys chars wheel gamepad`, buttons being
+    // some of `LMR`, lists comma separated, `-` when empty, and chars code
+    // points
+    pub fn to_text(&self) -> String {
+        fn list(items: Vec<String>) -> String {
+            if items.is_empty() {
+                String::from("-")
+            } else {
+                items.join(",")
+            }
+        }
+
+        let buttons = [(self.left, 'L'), (self.middle, 'M'), (self.right, 'R')]
+            .iter()
+            .filter(|(pressed, _)| *pressed)
+            .map(|(_, button)| button.to_string())
+            .collect();
+        let keys = self.keys.iter().filter_map(|&key| key_name(key));
+        let chars = self.chars.iter().map(|&char| (char as u32).to_string());
+        let wheel = self
+            .wheel
+            .iter()
+            .map(|wheel| format!("{}:{}", wheel.x, wheel.y));
+        let gamepad = self.gamepad.iter().filter_map(GamepadEvent::to_text);
+
+        format!(
+            "{} {} {} {} {} {} {} {} {} {} {}",
+            self.time.as_millis(),
+            self.size.x,
+            self.size.y,
+            self.position.x,
+            self.position.y,
+            list(buttons),
+            self.mods.bits(),
+            list(keys.map(String::from).collect()),
+            list(chars.collect()),
+            list(wheel.collect()),
+            list(gamepad.collect()),
+        )
+    }
+
+    pub fn from_text(line: &str) -> Option<Self> {
+        fn list<T>(word: &str, parse: impl Fn(&str) -> Option<T>) -> Option<Vec<T>> {
+            if word == "-" {
+                Some(Vec::new())
+            } else {
+                word.split(',').map(parse).collect()
+            }
+        }
+
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let float = |i: usize| words.get(i)?.parse::<f32>().ok();
+
+        if words.len() != 11 {
+            return None;
+        }
+
+        Some(Self {
+            time:     Duration::from_millis(words[0].parse().ok()?),
+            size:     [float(1)?, float(2)?].into(),
+            position: [float(3)?, float(4)?].into(),
+            left:     words[5].contains('L'),
+            middle:   words[5].contains('M'),
+            right:    words[5].contains('R'),
+            mods:     KeyMods::from_bits_truncate(words[6].parse().ok()?),
+            keys:     list(words[7], key_from_name)?,
+            chars:    list(words[8], |char| std::char::from_u32(char.parse().ok()?))?,
+            wheel:    list(words[9], |wheel| {
+                let mut wheel = wheel.split(':').map(|x| x.parse().ok());
+
+                Some([wheel.next()??, wheel.next()??].into())
+            })?,
+            gamepad:  list(words[10], GamepadEvent::from_text)?,
+        })
+    }
+}
@@ -0,0 +1,134 @@
+use super::*;
+
+macro_rules! names {
+    ($Type:ident $name:ident $from_name:ident [$($Variant:ident)*]) => {
+        pub fn $name(value: $Type) -> Option<&'static str> {
+            match value {
+                $($Type::$Variant => Some(stringify!($Variant)),)*
+                _ => None,
+            }
+        }
+
+        pub fn $from_name(name: &str) -> Option<$Type> {
+            [$($Type::$Variant,)*]
+                .iter()
+                .copied()
+                .find(|&value| $name(value) == Some(name))
+        }
+    };
+}
+
+names!(Button button_name button_from_name [
+    South East North West C Z
+    LeftTrigger LeftTrigger2 RightTrigger RightTrigger2
+    Select Start Mode LeftThumb RightThumb
+    DPadUp DPadDown DPadLeft DPadRight
+]);
+
+names!(Axis axis_name axis_from_name [
+    LeftStickX LeftStickY LeftZ RightStickX RightStickY RightZ DPadX DPadY
+]);
+
+// Received between frames, and recorded with them
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GamepadEvent {
+    Down(Button),
+    Up(Button),
+    Axis(Axis, f32),
+}
+
+impl GamepadEvent {
+    // E.g. `+South`, `-South` or `LeftStickX:0.5`
+    pub fn to_text(&self) -> Option<String> {
+        match *self {
+            Self::Down(button) => Some(format!("+{}", button_name(button)?)),
+            Self::Up(button) => Some(format!("-{}", button_name(button)?)),
+            Self::Axis(axis, value) => Some(format!("{}:{}", axis_name(axis)?, value)),
+        }
+    }
+
+    pub fn from_text(text: &str) -> Option<Self> {
+        if let Some(button) = text.strip_prefix('+') {
+            Some(Self::Down(button_from_name(button)?))
+        } else if let Some(button) = text.strip_prefix('-') {
+            Some(Self::Up(button_from_name(button)?))
+        } else {
+            let mut axis = text.split(':');
+
+            Some(Self::Axis(
+                axis_from_name(axis.next()?)?,
+                axis.next()?.parse().ok()?,
+            ))
+        }
+    }
+}
+
+// Inputs of all connected gamepads are merged
+#[derive(Clone, Debug)]
+pub struct Gamepad {
+    axes:    HashMap<Axis, f32>,
+    held:    HashSet<Button>,
+    pressed: Vec<Button>,
+}
+
+impl Gamepad {
+    pub fn new() -> Self {
+        Self {
+            axes:    HashMap::new(),
+            held:    HashSet::new(),
+            pressed: Vec::new(),
+        }
+    }
+
+    // Buttons pressed since the previous frame are fresh for this one
+    pub fn update(&mut self, frame: &Frame) {
+        self.pressed.clear();
+
+        for &event in &frame.gamepad {
+            match event {
+                GamepadEvent::Down(button) => {
+                    self.held.insert(button);
+                    self.pressed.push(button);
+                }
+                GamepadEvent::Up(button) => {
+                    self.held.remove(&button);
+                }
+                GamepadEvent::Axis(axis, value) => {
+                    self.axes.insert(axis, value);
+                }
+            }
+        }
+    }
+
+    pub fn is_pressed(&self, button: Button) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    pub fn is_held(&self, button: Button) -> bool {
+        self.held.contains(&button)
+    }
+
+    // Screen oriented, y going down
+    pub fn left_stick(&self) -> Point {
+        self.stick(Axis::LeftStickX, Axis::LeftStickY)
+    }
+
+    pub fn right_stick(&self) -> Point {
+        self.stick(Axis::RightStickX, Axis::RightStickY)
+    }
+
+    // Radial deadzone, rescaled so that the stick still goes from 0 to 1
+    fn stick(&self, x: Axis, y: Axis) -> Point {
+        let value = |axis| self.axes.get(&axis).copied().unwrap_or_default();
+        let (x, y) = (value(x), -value(y));
+        let length = (x * x + y * y).sqrt();
+
+        if length < DEADZONE {
+            [0., 0.].into()
+        } else {
+            let scale = ((length - DEADZONE) / (1. - DEADZONE)).min(1.) / length;
+
+            [x * scale, y * scale].into()
+        }
+    }
+}
@@ -1,45 +1,144 @@
 use super::*;
 
+// A held key repeats after `delay`, then every `rate`
+#[derive(Copy, Clone, Debug)]
+struct Key {
+    since:   Duration,
+    repeats: u32,
+    down:    bool,
+    repeat:  bool,
+}
+
+impl Key {
+    fn new(now: Duration) -> Self {
+        Self {
+            since:   now,
+            repeats: 0,
+            down:    true,
+            repeat:  false,
+        }
+    }
+
+    fn update(&mut self, now: Duration, (delay, rate): (Duration, Duration)) {
+        let elapsed = now.saturating_sub(self.since);
+        let repeats = if elapsed < delay {
+            0
+        } else {
+            1 + ((elapsed - delay).as_millis() / rate.as_millis().max(1)) as u32
+        };
+
+        self.down = false;
+        self.repeat = repeats > self.repeats;
+        self.repeats = repeats;
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Keyboard {
-    keys: HashMap<KeyCode, (bool, Instant)>,
-    mods: KeyMods,
+    keys:     HashMap<KeyCode, Key>,
+    released: Vec<KeyCode>,
+    mods:     KeyMods,
+    chars:    Vec<char>,
+    actions:  Vec<Action>,
+    queued:   Vec<Action>,
+    keymap:   Keymap,
 }
 
 impl Keyboard {
     pub fn new() -> Self {
         Self {
-            keys: Default::default(),
-            mods: KeyMods::empty(),
+            keys:     Default::default(),
+            released: Vec::new(),
+            mods:     KeyMods::empty(),
+            chars:    Vec::new(),
+            actions:  Vec::new(),
+            queued:   Vec::new(),
+            keymap:   Keymap::new(),
         }
     }
 
-    pub fn update(&mut self, ctx: &mut Context) {
-        let now = Instant::now();
-        let keys = ggez::input::keyboard::pressed_keys(ctx);
-        self.mods = ggez::input::keyboard::active_mods(ctx);
+    // Keys repeat according to the time of the frame, so that replays do too
+    pub fn update(&mut self, frame: &Frame) {
+        let now = frame.time;
+        let keys = &frame.keys;
+        self.mods = frame.mods;
+        self.chars = frame
+            .chars
+            .iter()
+            .copied()
+            .filter(|char| !char.is_control())
+            .collect();
+        self.actions = std::mem::take(&mut self.queued);
 
-        // No keys are fresh now, remove olds
-        self.keys.retain(|_, (bool, instant)| {
-            *bool = false;
-            (now - *instant) < KEYBOARD_DEBOUNCE
-        });
+        self.released = self
+            .keys
+            .keys()
+            .filter(|key| !keys.contains(key))
+            .copied()
+            .collect();
+        self.keys.retain(|key, _| keys.contains(key));
 
-        // Add missing keys
+        let repeat = self.keymap.repeat();
         for key in keys {
-            if self.keys.get(key).is_none() {
-                self.keys.insert(*key, (true, now));
+            match self.keys.get_mut(key) {
+                Some(state) => state.update(now, repeat),
+                None => {
+                    self.keys.insert(*key, Key::new(now));
+                }
             }
         }
     }
 
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    // Only on the frame the key goes down
     pub fn is_pressed(&self, key: KeyCode) -> bool {
-        match self.keys.get(&key) {
-            Some((true, _)) => true,
-            _ => false,
+        self.keys.get(&key).map_or(false, |key| key.down)
+    }
+
+    // When the key goes down, then repeatedly while it is held
+    pub fn is_repeated(&self, key: KeyCode) -> bool {
+        self.keys
+            .get(&key)
+            .map_or(false, |key| key.down || key.repeat)
+    }
+
+    pub fn is_held(&self, key: KeyCode) -> bool {
+        self.keys.contains_key(&key)
+    }
+
+    pub fn is_released(&self, key: KeyCode) -> bool {
+        self.released.contains(&key)
+    }
+
+    // Triggers `action` on the next update, as if its chord was pressed
+    pub fn trigger(&mut self, action: Action) {
+        self.queued.push(action);
+    }
+
+    pub fn action(&self, action: Action) -> bool {
+        self.actions.contains(&action) || self.keymap.is_pressed(action, self)
+    }
+
+    // Which of the chords of `action` is pressed, the first when triggered
+    pub fn action_index(&self, action: Action) -> Option<usize> {
+        if self.actions.contains(&action) {
+            Some(0)
+        } else {
+            self.keymap.pressed(action, self)
         }
     }
 
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+    }
+
     pub fn is_active(&self, mods: KeyMods) -> bool {
         self.mods.contains(mods)
     }
@@ -51,4 +150,8 @@ impl Keyboard {
     pub fn shift(&self) -> bool {
         self.is_active(KeyMods::SHIFT)
     }
+
+    pub fn alt(&self) -> bool {
+        self.is_active(KeyMods::ALT)
+    }
 }
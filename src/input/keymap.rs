@@ -0,0 +1,293 @@
+use super::*;
+
+macro_rules! keys {
+    ($($Key:ident)*) => {
+        pub fn key_name(key: KeyCode) -> Option<&'static str> {
+            match key {
+                $(KeyCode::$Key => Some(stringify!($Key)),)*
+                _ => None,
+            }
+        }
+
+        pub fn key_from_name(name: &str) -> Option<KeyCode> {
+            [$(KeyCode::$Key,)*]
+                .iter()
+                .copied()
+                .find(|&key| key_name(key).map(|key| key.eq_ignore_ascii_case(name)) == Some(true))
+        }
+    };
+}
+
+keys!(
+    A B C D E F G H I J K L M N O P Q R S T U V W X Y Z
+    Key0 Key1 Key2 Key3 Key4 Key5 Key6 Key7 Key8 Key9
+    F1 F2 F3 F4 F5 F6 F7 F8 F9 F10 F11 F12
+    Escape Tab Return Space Back Delete Insert Home End PageUp PageDown
+    Up Down Left Right
+    Plus Minus Equals Comma Period Slash Backslash Semicolon Apostrophe
+    LBracket RBracket Grave
+);
+
+// A key with exactly these modifiers
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Chord {
+    key:   KeyCode,
+    ctrl:  bool,
+    shift: bool,
+    alt:   bool,
+}
+
+impl Chord {
+    // E.g. `Ctrl+Shift+Z`, case insensitive
+    pub fn from_name(name: &str) -> Option<Self> {
+        let mut parts = name.split('+').collect::<Vec<_>>();
+        let key = key_from_name(parts.pop()?)?;
+        let mut chord = Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        };
+
+        for part in parts {
+            match part.to_lowercase().as_str() {
+                "ctrl" => chord.ctrl = true,
+                "shift" => chord.shift = true,
+                "alt" => chord.alt = true,
+                _ => return None,
+            }
+        }
+
+        Some(chord)
+    }
+
+    pub fn name(&self) -> String {
+        let mut name = String::new();
+
+        if self.ctrl {
+            name.push_str("Ctrl+");
+        }
+        if self.shift {
+            name.push_str("Shift+");
+        }
+        if self.alt {
+            name.push_str("Alt+");
+        }
+        name.push_str(key_name(self.key).unwrap_or("?"));
+
+        name
+    }
+
+    pub fn key(&self) -> KeyCode {
+        self.key
+    }
+
+    pub fn mods(&self) -> KeyMods {
+        let mut mods = KeyMods::empty();
+        mods.set(KeyMods::CTRL, self.ctrl);
+        mods.set(KeyMods::SHIFT, self.shift);
+        mods.set(KeyMods::ALT, self.alt);
+
+        mods
+    }
+
+    pub fn is_pressed(&self, keyboard: &Keyboard, repeat: bool) -> bool {
+        let pressed = if repeat {
+            keyboard.is_repeated(self.key)
+        } else {
+            keyboard.is_pressed(self.key)
+        };
+
+        pressed
+            && keyboard.ctrl() == self.ctrl
+            && keyboard.shift() == self.shift
+            && keyboard.alt() == self.alt
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: HashMap<Action, Vec<Chord>>,
+    repeat:   (Duration, Duration),
+}
+
+impl Keymap {
+    pub fn new() -> Self {
+        let mut keymap = Self {
+            bindings: HashMap::new(),
+            repeat:   (KEYBOARD_REPEAT_DELAY, KEYBOARD_REPEAT_RATE),
+        };
+
+        for &action in Action::all() {
+            let chords = action
+                .defaults()
+                .iter()
+                .filter_map(|chord| Chord::from_name(chord));
+            keymap.bindings.insert(action, chords.collect());
+        }
+
+        keymap.resolve();
+        keymap
+    }
+
+    // Lines of the keymap file bind an action to its chords, replacing the
+    // defaults (e.g. `Undo Ctrl+Z Ctrl+Back`, or `Undo` to unbind it), or set
+    // the initial delay and rate of repeated actions in milliseconds (e.g.
+    // `Repeat 400 50`)
+    pub fn load() -> Self {
+        let mut keymap = Self::new();
+
+        if let Ok(text) = std::fs::read_to_string(KEYMAP) {
+            keymap.read(&text);
+            keymap.resolve();
+        }
+
+        keymap
+    }
+
+    pub fn chords(&self, action: Action) -> &[Chord] {
+        self.bindings
+            .get(&action)
+            .map(|chords| chords.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn is_pressed(&self, action: Action, keyboard: &Keyboard) -> bool {
+        self.pressed(action, keyboard).is_some()
+    }
+
+    // The index of the pressed chord among those of `action`
+    pub fn pressed(&self, action: Action, keyboard: &Keyboard) -> Option<usize> {
+        self.chords(action)
+            .iter()
+            .position(|chord| chord.is_pressed(keyboard, action.repeats()))
+    }
+
+    pub fn repeat(&self) -> (Duration, Duration) {
+        self.repeat
+    }
+
+    // Comments start with `#`
+    fn read(&mut self, text: &str) {
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let mut words = line.split_whitespace();
+            let name = match words.next() {
+                Some(name) => name,
+                None => continue,
+            };
+
+            if name == "Repeat" {
+                let millis = words
+                    .map(|word| word.parse().map(Duration::from_millis))
+                    .collect::<Result<Vec<_>, _>>();
+
+                match millis.as_deref() {
+                    Ok(&[delay, rate]) => self.repeat = (delay, rate),
+                    _ => println!("{}:{}: expected Repeat <delay> <rate>", KEYMAP, i + 1),
+                }
+                continue;
+            }
+
+            let action = match Action::from_name(name) {
+                Some(action) => action,
+                None => {
+                    println!("{}:{}: unknown action {}", KEYMAP, i + 1, name);
+                    continue;
+                }
+            };
+
+            let chords = words
+                .filter_map(|chord| {
+                    let parsed = Chord::from_name(chord);
+                    if parsed.is_none() {
+                        println!("{}:{}: unknown chord {}", KEYMAP, i + 1, chord);
+                    }
+                    parsed
+                })
+                .collect();
+
+            self.bindings.insert(action, chords);
+        }
+    }
+
+    // A chord bound to actions of overlapping scopes is kept for the first one
+    fn resolve(&mut self) {
+        let mut bound = Vec::<(Chord, Action)>::new();
+
+        for &action in Action::all() {
+            let chords = self.bindings.remove(&action).unwrap_or_default();
+            let chords = chords
+                .into_iter()
+                .filter(|chord| {
+                    let conflict = bound.iter().find(|(other_chord, other)| {
+                        other_chord == chord && other.scope().overlaps(action.scope())
+                    });
+
+                    if let Some((_, other)) = conflict {
+                        println!(
+                            "{}: {} is already bound to {}, ignored for {}",
+                            KEYMAP,
+                            chord.name(),
+                            other.name(),
+                            action.name()
+                        );
+                        false
+                    } else {
+                        bound.push((*chord, action));
+                        true
+                    }
+                })
+                .collect();
+
+            self.bindings.insert(action, chords);
+        }
+    }
+
+    // One column per scope
+    pub fn draw(&self, ctx: &mut Context) {
+        let (w, h) = drawable_size(ctx);
+
+        MeshBuilder::new()
+            .rectangle(
+                DrawMode::fill(),
+                [0., 0., w, h].into(),
+                Color::new(0., 0., 0., 0.85),
+            )
+            .unwrap()
+            .build(ctx)
+            .unwrap()
+            .draw(ctx, DrawParam::new())
+            .unwrap();
+
+        let scopes = [Scope::Global, Scope::Pencil, Scope::Selection];
+        let width = w / scopes.len() as f32;
+
+        for (i, scope) in scopes.iter().enumerate() {
+            let mut text = format!("{} (rebind in {})\n\n", scope.name(), KEYMAP);
+
+            for action in Action::all()
+                .iter()
+                .filter(|action| action.scope() == *scope)
+            {
+                let chords = self
+                    .chords(*action)
+                    .iter()
+                    .map(|chord| chord.name())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                text.push_str(&format!(
+                    "{}  {}  - {}\n",
+                    action.name(),
+                    chords,
+                    action.description()
+                ));
+            }
+
+            Text::new(text)
+                .draw(ctx, DrawParam::new().dest([20. + i as f32 * width, 20.]))
+                .unwrap();
+        }
+    }
+}
@@ -1,9 +1,25 @@
 use super::*;
 
+mod cells;
+mod frame;
+mod gamepad;
 mod keyboard;
+mod keymap;
 mod mouse;
+mod recording;
+mod script;
 mod selection;
+mod source;
+mod text_input;
 
+pub use cells::*;
+pub use frame::*;
+pub use gamepad::*;
 pub use keyboard::*;
+pub use keymap::*;
 pub use mouse::*;
+pub use recording::*;
+pub use script::*;
 pub use selection::*;
+pub use source::*;
+pub use text_input::*;
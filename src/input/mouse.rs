@@ -45,7 +45,9 @@ impl Button {
 #[derive(Copy, Clone, Debug)]
 pub struct Mouse {
     position: Point,
+    previous: Point,
     left:     Option<Button>,
+    middle:   Option<Button>,
     right:    Option<Button>,
 }
 
@@ -66,22 +68,29 @@ macro_rules! clicks {
 }
 
 impl Mouse {
-    clicks!(left left_click left_drag right right_click right_drag);
+    clicks!(
+        left left_click left_drag
+        middle middle_click middle_drag
+        right right_click right_drag
+    );
 
     pub fn new() -> Self {
         Self {
             left:     None,
+            middle:   None,
             right:    None,
             position: [0., 0.].into(),
+            previous: [0., 0.].into(),
         }
     }
 
-    pub fn update(&mut self, ctx: &mut Context) {
-        self.position = ggez::input::mouse::position(ctx);
+    pub fn update(&mut self, frame: &Frame) {
+        self.previous = self.position;
+        self.position = frame.position;
 
         macro_rules! buttons {
-            ($($button:ident ($Button:ident))*) => { $(
-                if ggez::input::mouse::button_pressed(ctx, MouseButton::$Button) {
+            ($($button:ident)*) => { $(
+                if frame.$button {
                     if let Some(button) = &mut self.$button {
                         button.update(self.position);
                     } else {
@@ -93,10 +102,19 @@ impl Mouse {
             )* };
         }
 
-        buttons!(left (Left) right (Right));
+        buttons!(left middle right);
     }
 
     pub fn position(&self) -> Point {
         self.position
     }
+
+    // Since the previous frame
+    pub fn motion(&self) -> Point {
+        [
+            self.position.x - self.previous.x,
+            self.position.y - self.previous.y,
+        ]
+        .into()
+    }
 }
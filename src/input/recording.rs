@@ -0,0 +1,56 @@
+use super::*;
+
+// Writes each frame as soon as it is recorded, so that a crash keeps it
+#[derive(Debug)]
+pub struct Recorder {
+    file: std::fs::File,
+}
+
+impl Recorder {
+    pub fn create(path: &str) -> std::io::Result<Self> {
+        let mut file = std::fs::File::create(path)?;
+        writeln!(file, "# {} frames per second", FPS)?;
+
+        Ok(Self { file })
+    }
+
+    pub fn record(&mut self, frame: &Frame) {
+        if let Err(err) = writeln!(self.file, "{}", frame.to_text()) {
+            println!("Cannot record frame: {}", err);
+        }
+    }
+}
+
+// Comments start with `#`
+#[derive(Clone, Debug)]
+pub struct Replay {
+    frames: std::vec::IntoIter<Frame>,
+}
+
+impl Replay {
+    pub fn load(path: &str) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let frames = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .filter_map(|(i, line)| {
+                let frame = Frame::from_text(line);
+                if frame.is_none() {
+                    println!("{}:{}: invalid frame", path, i + 1);
+                }
+                frame
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            frames: frames.into_iter(),
+        })
+    }
+}
+
+impl InputSource for Replay {
+    fn next(&mut self) -> Option<Frame> {
+        self.frames.next()
+    }
+}
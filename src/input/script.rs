@@ -0,0 +1,140 @@
+use super::*;
+
+// Frames built step by step, e.g. to drive the editor in tests. Each step
+// lasts at least a frame, keys and buttons being released afterwards
+#[derive(Clone, Debug)]
+pub struct Script {
+    frames:  std::collections::VecDeque<Frame>,
+    current: Frame,
+}
+
+impl Script {
+    pub fn new(size: impl Into<Point>) -> Self {
+        Self {
+            frames:  Default::default(),
+            current: Frame {
+                time:     Duration::from_secs(0),
+                size:     size.into(),
+                keys:     Vec::new(),
+                mods:     KeyMods::empty(),
+                chars:    Vec::new(),
+                position: [0., 0.].into(),
+                left:     false,
+                middle:   false,
+                right:    false,
+                wheel:    Vec::new(),
+                gamepad:  Vec::new(),
+            },
+        }
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    // Pushes the current state as a frame, events only lasting for it
+    pub fn frame(mut self) -> Self {
+        self.frames.push_back(self.current.clone());
+        self.current.time += Duration::from_secs_f32(RATE);
+        self.current.chars.clear();
+        self.current.wheel.clear();
+        self.current.gamepad.clear();
+
+        self
+    }
+
+    pub fn wait(mut self, frames: usize) -> Self {
+        for _ in 0..frames {
+            self = self.frame();
+        }
+
+        self
+    }
+
+    // E.g. `Ctrl+Z`
+    pub fn press(self, chord: &str) -> Self {
+        self.hold(chord, 1)
+    }
+
+    pub fn hold(mut self, chord: &str, frames: usize) -> Self {
+        let chord = Chord::from_name(chord).expect("Unknown chord");
+        self.current.keys = vec![chord.key()];
+        self.current.mods = chord.mods();
+        self = self.wait(frames);
+        self.current.keys.clear();
+        self.current.mods = KeyMods::empty();
+
+        self.frame()
+    }
+
+    pub fn type_text(mut self, text: &str) -> Self {
+        self.current.chars = text.chars().collect();
+
+        self.frame()
+    }
+
+    pub fn move_to(mut self, position: impl Into<Point>) -> Self {
+        self.current.position = position.into();
+
+        self.frame()
+    }
+
+    pub fn click(self, position: impl Into<Point>) -> Self {
+        let position = position.into();
+
+        self.drag(position, position)
+    }
+
+    pub fn right_click(self, position: impl Into<Point>) -> Self {
+        let position = position.into();
+
+        self.button(|frame| &mut frame.right, position, position)
+    }
+
+    // Presses the left button at `from` and releases it at `to`
+    pub fn drag(self, from: impl Into<Point>, to: impl Into<Point>) -> Self {
+        self.button(|frame| &mut frame.left, from.into(), to.into())
+    }
+
+    pub fn middle_drag(self, from: impl Into<Point>, to: impl Into<Point>) -> Self {
+        self.button(|frame| &mut frame.middle, from.into(), to.into())
+    }
+
+    fn button(mut self, button: fn(&mut Frame) -> &mut bool, from: Point, to: Point) -> Self {
+        self = self.move_to(from);
+        *button(&mut self.current) = true;
+        self = self.frame();
+        self.current.position = to;
+        self = self.frame();
+        *button(&mut self.current) = false;
+
+        self.frame()
+    }
+
+    pub fn wheel(mut self, wheel: impl Into<Point>) -> Self {
+        self.current.wheel.push(wheel.into());
+
+        self.frame()
+    }
+
+    // E.g. to hold a button or to tilt a stick
+    pub fn gamepad(mut self, event: GamepadEvent) -> Self {
+        self.current.gamepad.push(event);
+
+        self.frame()
+    }
+
+    pub fn press_button(mut self, button: Button) -> Self {
+        self.current.gamepad.push(GamepadEvent::Down(button));
+        self = self.frame();
+        self.current.gamepad.push(GamepadEvent::Up(button));
+
+        self.frame()
+    }
+}
+
+impl InputSource for Script {
+    fn next(&mut self) -> Option<Frame> {
+        self.frames.pop_front()
+    }
+}
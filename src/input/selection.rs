@@ -100,10 +100,31 @@ impl<T: IntoI16<Output = i16>> ButtonSelection<T> {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+impl ButtonSelection {
+    pub fn from_ranges((x, y): (Range<i16>, Range<i16>)) -> Self {
+        let start = Point {
+            x: x.start as f32,
+            y: y.start as f32,
+        };
+        let end = Point {
+            x: (x.end - 1) as f32,
+            y: (y.end - 1) as f32,
+        };
+
+        if start == end {
+            Self::Start(start)
+        } else {
+            Self::Select((start, end))
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub enum Selection {
     Selecting(ButtonSelection),
-    Selected(ButtonSelection),
+    Adding((Cells, ButtonSelection)),
+    Subtracting((Cells, ButtonSelection)),
+    Selected(Cells),
     None,
 }
 
@@ -112,9 +133,24 @@ impl Selection {
         Self::Selecting(ButtonSelection::Start(position))
     }
 
-    pub fn events(&mut self, mouse: &Mouse, viewport: Viewport, persist: bool) {
+    pub fn selected(cells: Cells) -> Self {
+        if cells.is_empty() {
+            Self::None
+        } else {
+            Self::Selected(cells)
+        }
+    }
+
+    pub fn events(
+        &mut self,
+        keyboard: &Keyboard,
+        mouse: &Mouse,
+        viewport: Viewport,
+        persist: bool,
+    ) {
         let position = viewport.coordinates(mouse.position());
         let left = mouse.left();
+        let click = mouse.left_click().is_some();
         let right = mouse.right();
 
         match self {
@@ -122,20 +158,38 @@ impl Selection {
                 if left {
                     selection.select(position);
                 } else if persist {
-                    *self = Self::Selected(*selection)
+                    *self = Self::selected(Cells::from_ranges(selection.ranges()))
                 } else {
                     self.clear()
                 },
-            Self::Selected(_) =>
-                if persist {
-                    if right {
-                        self.clear()
-                    }
+            Self::Adding((cells, selection)) =>
+                if left {
+                    selection.select(position);
                 } else {
+                    let cells = cells.union(&Cells::from_ranges(selection.ranges()));
+                    *self = Self::selected(cells)
+                },
+            Self::Subtracting((cells, selection)) =>
+                if left {
+                    selection.select(position);
+                } else {
+                    let cells = cells.difference(&Cells::from_ranges(selection.ranges()));
+                    *self = Self::selected(cells)
+                },
+            // Without persistence (i.e. with a pencil), the selection remains until
+            // painting starts
+            Self::Selected(cells) =>
+                if right {
                     self.clear()
+                } else if click && !persist {
+                    *self = Self::start(position)
+                } else if click && keyboard.shift() {
+                    *self = Self::Adding((cells.clone(), ButtonSelection::Start(position)))
+                } else if click && keyboard.ctrl() {
+                    *self = Self::Subtracting((cells.clone(), ButtonSelection::Start(position)))
                 },
             Self::None =>
-                if left {
+                if click {
                     *self = Self::start(position);
                 },
         }
@@ -145,10 +199,31 @@ impl Selection {
         *self = Self::None;
     }
 
+    pub fn cancel(&mut self) {
+        *self = match self {
+            Self::Adding((cells, _)) => Self::Selected(cells.clone()),
+            Self::Subtracting((cells, _)) => Self::Selected(cells.clone()),
+            Self::Selected(cells) => Self::Selected(cells.clone()),
+            _ => Self::None,
+        };
+    }
+
     pub fn selection(&self) -> Option<ButtonSelection> {
         match *self {
             Self::Selecting(selection) => Some(selection),
-            Self::Selected(selection) => Some(selection),
+            Self::Adding((_, selection)) => Some(selection),
+            Self::Subtracting((_, selection)) => Some(selection),
+            Self::Selected(_) => None,
+            Self::None => None,
+        }
+    }
+
+    pub fn cells(&self) -> Option<&Cells> {
+        match self {
+            Self::Selecting(_) => None,
+            Self::Adding((cells, _)) => Some(cells),
+            Self::Subtracting((cells, _)) => Some(cells),
+            Self::Selected(cells) => Some(cells),
             Self::None => None,
         }
     }
@@ -0,0 +1,66 @@
+use super::*;
+
+// Where the frames come from: the window, a recording or a script
+pub trait InputSource: std::fmt::Debug {
+    // Live sources read the context before each frame, others ignore it
+    fn poll(&mut self, _ctx: &mut Context, _time: Duration) {}
+
+    fn text_input(&mut self, _char: char) {}
+
+    fn wheel(&mut self, _wheel: Point) {}
+
+    fn gamepad(&mut self, _event: GamepadEvent) {}
+
+    // Whether the frames come from the window
+    fn is_live(&self) -> bool {
+        false
+    }
+
+    // `None` once the source is exhausted
+    fn next(&mut self) -> Option<Frame>;
+}
+
+// Characters, wheel moves and gamepad events are received between polls
+#[derive(Clone, Default, Debug)]
+pub struct LiveInput {
+    chars:   Vec<char>,
+    wheel:   Vec<Point>,
+    gamepad: Vec<GamepadEvent>,
+    frame:   Option<Frame>,
+}
+
+impl LiveInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl InputSource for LiveInput {
+    fn poll(&mut self, ctx: &mut Context, time: Duration) {
+        let chars = std::mem::take(&mut self.chars);
+        let wheel = std::mem::take(&mut self.wheel);
+        let gamepad = std::mem::take(&mut self.gamepad);
+
+        self.frame = Some(Frame::live(ctx, time, chars, wheel, gamepad));
+    }
+
+    fn text_input(&mut self, char: char) {
+        self.chars.push(char);
+    }
+
+    fn wheel(&mut self, wheel: Point) {
+        self.wheel.push(wheel);
+    }
+
+    fn gamepad(&mut self, event: GamepadEvent) {
+        self.gamepad.push(event);
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+
+    fn next(&mut self) -> Option<Frame> {
+        self.frame.take()
+    }
+}
@@ -0,0 +1,32 @@
+use super::*;
+
+#[derive(Clone, Default, Debug)]
+pub struct TextInput {
+    text: String,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+        }
+    }
+
+    pub fn events(&mut self, keyboard: &Keyboard) {
+        self.text.extend(keyboard.chars());
+
+        if keyboard.is_repeated(KeyCode::Back) {
+            self.text.pop();
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn draw(&self, ctx: &mut Context, label: &str, position: impl Into<Point>) {
+        Text::new(format!("{}: {}_", label, self.text))
+            .draw(ctx, DrawParam::new().dest(position))
+            .unwrap();
+    }
+}
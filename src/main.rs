@@ -3,6 +3,8 @@ mod editor;
 mod graphics;
 mod imports;
 mod input;
+#[cfg(test)]
+mod tests;
 
 use imports::*;
 
@@ -29,6 +31,24 @@ fn main() {
         .build()
         .expect("Could not create ggez context!");
 
-    let editor = Editor::new(&mut ctx);
+    let mut editor = Editor::new(&mut ctx);
+    // Saving without a scene argument must not overwrite the default scene
+    if Path::new(SCENE).exists() {
+        editor.open(SCENE);
+    }
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" | "--replay" => match args.next() {
+                Some(path) if arg == "--record" => editor.record(&path),
+                Some(path) => editor.replay(&path),
+                None => println!("Missing file after {}", arg),
+            },
+            path if !path.starts_with("--") => editor.open(path),
+            _ => println!("Usage: rpg [--record <file> | --replay <file>] [scene]"),
+        }
+    }
+
     event::run(ctx, event_loop, editor);
 }
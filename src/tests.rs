@@ -0,0 +1,529 @@
+use super::*;
+
+const SIZE: [f32; 2] = [1366., 768.];
+
+// The center of `cell` on screen, at the initial zoom and origin, with the
+// default tiles
+fn cell(x: i16, y: i16) -> Point {
+    let cell = Catalogue::default_tileset().cell();
+
+    [(x as f32 + 0.5) * cell.x, (y as f32 + 0.5) * cell.y].into()
+}
+
+// Right clicks `at`, over a cell with content and without pencil, and clicks
+// `item` in the menu
+fn menu(script: Script, at: impl Into<Point>, item: MenuItem) -> Script {
+    let at = at.into();
+    let items = ContextMenu::items(true, true, false);
+    let menu = ContextMenu::new(at, [0, 0].into(), items, SIZE.into());
+
+    script
+        .right_click(at)
+        .click(menu.item_position(item).unwrap())
+}
+
+// The initial scene has floors on 0..5, 0..5, and the keymap, grid and
+// prefabs are the defaults, whatever the working directory holds
+fn run(script: Script) -> Editor {
+    let mut editor = Editor::with_input(SIZE.into(), script);
+    while editor.step() {}
+
+    editor
+}
+
+#[test]
+fn delete_selection() {
+    let editor = run(Script::new(SIZE)
+        .drag(cell(0, 0), cell(1, 1))
+        .press("Delete"));
+    let floors = &editor.scene().floors;
+
+    assert!(!floors.contains_key(&[0, 0].into()));
+    assert!(!floors.contains_key(&[1, 1].into()));
+    assert!(floors.contains_key(&[2, 2].into()));
+    assert_eq!(floors.len(), 21);
+}
+
+#[test]
+fn undo_and_redo() {
+    let script = Script::new(SIZE)
+        .drag(cell(0, 0), cell(4, 4))
+        .press("Delete");
+
+    let editor = run(script.clone().press("Ctrl+Z"));
+    assert_eq!(editor.scene().floors.len(), 25);
+
+    let editor = run(script.press("Ctrl+Z").press("Ctrl+Shift+Z"));
+    assert!(editor.scene().floors.is_empty());
+}
+
+#[test]
+fn held_undo_repeats() {
+    let mut script = Script::new(SIZE);
+    for x in 0..3 {
+        script = script.drag(cell(x, 0), cell(x, 0)).press("Delete");
+    }
+
+    let editor = run(script.clone().press("Ctrl+Z"));
+    assert_eq!(editor.scene().floors.len(), 23);
+
+    let editor = run(script.hold("Ctrl+Z", 30));
+    assert_eq!(editor.scene().floors.len(), 25);
+}
+
+#[test]
+fn paint_with_palette_pencil() {
+    // The first floor of the docked palette
+    let panel = SIZE[0] - PANEL_WIDTH;
+    let editor = run(Script::new(SIZE)
+        .click([panel + 24., 96.])
+        .drag(cell(10, 10), cell(12, 10)));
+    let floors = &editor.scene().floors;
+    let floor = Some(&(FloorEnum::all()[0], North));
+
+    assert_eq!(floors.get(&[10, 10].into()), floor);
+    assert_eq!(floors.get(&[12, 10].into()), floor);
+    assert_eq!(floors.get(&[12, 11].into()), None);
+}
+
+#[test]
+fn gamepad_cycles_palette() {
+    let editor = run(Script::new(SIZE)
+        .press_button(Button::RightTrigger)
+        .drag(cell(10, 10), cell(11, 10)));
+    let floors = &editor.scene().floors;
+    let floor = Some(&(FloorEnum::all()[0], North));
+
+    assert_eq!(floors.get(&[10, 10].into()), floor);
+    assert_eq!(floors.get(&[11, 10].into()), floor);
+}
+
+#[test]
+fn replace_reports_count() {
+    // Cracks3 becomes the first entry of the docked palette
+    let panel = SIZE[0] - PANEL_WIDTH;
+    let script = Script::new(SIZE)
+        .move_to([panel + 24., 96.])
+        .type_text("cracks3")
+        .click([panel + 24., 96.])
+        .move_to(cell(2, 2))
+        .press("Ctrl+H");
+
+    let editor = run(script.clone());
+    let floors = &editor.scene().floors;
+    assert!(floors.values().all(|&floor| floor == (Cracks3, North)));
+    assert_eq!(editor.status().last_message(), Some("Replaced 25 cells"));
+
+    let editor = run(script.press("Ctrl+Z"));
+    assert!(editor
+        .scene()
+        .floors
+        .values()
+        .all(|&floor| floor == (Floor, North)));
+}
+
+#[test]
+fn context_menu_delete() {
+    // Pick, then Delete
+    let editor = run(menu(Script::new(SIZE), cell(1, 1), MenuItem::Delete));
+    let floors = &editor.scene().floors;
+
+    assert!(!floors.contains_key(&[1, 1].into()));
+    assert_eq!(floors.len(), 24);
+}
+
+#[test]
+fn gamepad_erasing_keeps_stroke() {
+    // The cursor starts at the center, on 42, 24, and paints to 44, which is
+    // then erased
+    let script = Script::new(SIZE)
+        .press_button(Button::RightTrigger)
+        .gamepad(GamepadEvent::Down(Button::South))
+        .gamepad(GamepadEvent::Axis(Axis::LeftStickX, 1.))
+        .wait(7)
+        .gamepad(GamepadEvent::Axis(Axis::LeftStickX, 0.))
+        .gamepad(GamepadEvent::Down(Button::East))
+        .gamepad(GamepadEvent::Up(Button::South))
+        .gamepad(GamepadEvent::Up(Button::East));
+
+    let editor = run(script.clone());
+    let floors = &editor.scene().floors;
+    assert!(floors.contains_key(&[42, 24].into()));
+    assert!(floors.contains_key(&[43, 24].into()));
+    assert!(!floors.contains_key(&[44, 24].into()));
+    assert_eq!(floors.len(), 25 + 2);
+
+    // Undoing the erasing stroke, then the painting one
+    let editor = run(script.clone().press("Ctrl+Z"));
+    assert_eq!(editor.scene().floors.len(), 25 + 3);
+    let editor = run(script.press("Ctrl+Z").press("Ctrl+Z"));
+    assert_eq!(editor.scene().floors.len(), 25);
+}
+
+#[test]
+fn right_click_outside_deselects() {
+    // Only the clicked cell is deleted, even by the Delete key afterwards
+    let script = Script::new(SIZE).drag(cell(0, 0), cell(1, 1));
+    let editor = run(menu(script, cell(3, 3), MenuItem::Delete).press("Delete"));
+    let floors = &editor.scene().floors;
+
+    assert!(!floors.contains_key(&[3, 3].into()));
+    assert_eq!(floors.len(), 24);
+}
+
+#[test]
+fn middle_drag_pans() {
+    let pan = 10. * Catalogue::default_tileset().cell().x;
+    let editor = run(Script::new(SIZE).middle_drag([100., 100.], [100. + pan, 100.]));
+
+    assert_eq!(
+        editor.viewport().coordinates_i16(cell(10, 0)),
+        [0, 0].into()
+    );
+}
+
+#[test]
+fn wheel_inertia() {
+    // The scene glides 15 cells right
+    let editor = run(Script::new(SIZE).wheel([-8., 0.]).wait(60));
+
+    assert_eq!(
+        editor.viewport().coordinates_i16(cell(15, 0)),
+        [0, 0].into()
+    );
+}
+
+#[test]
+fn snapped_zoom_below_one() {
+    let mut viewport = Viewport::new(SIZE.into());
+    viewport.toggle_snap();
+
+    viewport.zoom(viewport.center(), -0.6);
+    viewport.animate(CAMERA_DURATION);
+    assert_eq!(viewport.scale(), 1. / 3.);
+
+    viewport.zoom(viewport.center(), 0.1);
+    viewport.animate(CAMERA_DURATION);
+    assert_eq!(viewport.scale(), 0.5);
+}
+
+#[test]
+fn status_message_expires() {
+    let mut status = StatusBar::new();
+    status.message(String::from("Saved"));
+
+    status.update(Duration::from_millis(2900));
+    assert_eq!(status.last_message(), Some("Saved"));
+    status.update(Duration::from_millis(100));
+    assert_eq!(status.last_message(), None);
+}
+
+#[test]
+fn viewport_cell_size() {
+    let mut viewport = Viewport::new(SIZE.into());
+    viewport.set_cell([32., 24.].into());
+
+    assert_eq!(viewport.coordinates_i16([40., 50.]), [1, 2].into());
+}
+
+#[test]
+fn split_panes() {
+    // The right pane pans 10 cells on its own
+    let half = ((SIZE[0] - PANEL_WIDTH) / 2.).floor();
+    let right = |x, y| [half + cell(x, y).x, cell(x, y).y];
+    let editor = run(Script::new(SIZE)
+        .press("Backslash")
+        .middle_drag(right(0, 5), right(10, 5)));
+    let panes = editor.panes();
+
+    assert_eq!(panes.len(), 2);
+    assert_eq!(panes[0].coordinates_i16(cell(2, 2)), [2, 2].into());
+    assert_eq!(panes[1].coordinates_i16(right(13, 3)), [3, 3].into());
+}
+
+#[test]
+fn zoom_to_selection() {
+    // The selected cell fills the scene, left of the panel, as much as the
+    // scale allows
+    let center = [
+        (SIZE[0] - PANEL_WIDTH) / 2.,
+        (SIZE[1] - StatusBar::HEIGHT) / 2.,
+    ];
+    let editor = run(Script::new(SIZE)
+        .click(cell(3, 3))
+        .press("Shift+Key0")
+        .wait(10));
+
+    assert_eq!(editor.viewport().scale(), SCALE_LIMIT);
+    assert_eq!(editor.viewport().coordinates_i16(center), [3, 3].into());
+}
+
+#[test]
+fn bookmark_jump() {
+    // Back to the origin after panning away
+    let initial = run(Script::new(SIZE)).viewport().bookmark();
+    let editor = run(Script::new(SIZE)
+        .press("Ctrl+Key1")
+        .middle_drag([100., 100.], [300., 300.])
+        .press("Key1")
+        .wait(10));
+
+    assert_eq!(editor.viewport().bookmark(), initial);
+}
+
+#[test]
+fn bookmarks_outside_history() {
+    // Undoing skips the bookmark set after the deletion, which still has to
+    // be saved
+    let editor = run(Script::new(SIZE)
+        .drag(cell(0, 0), cell(0, 0))
+        .press("Delete")
+        .press("Ctrl+Key1")
+        .press("Ctrl+Z"));
+
+    assert_eq!(editor.scene().floors.len(), 25);
+    assert!(editor.is_modified());
+}
+
+#[test]
+fn minimap_of_distant_cells() {
+    let mut scene = Scene::new();
+    scene.add_floor(Floor, North, (-30000..-29999, 0..1));
+    scene.add_floor(Floor, North, (30000..30001, 0..1));
+    let (w, h, rgba) = Minimap::pixels(&scene, &scene.cells(Show::new()).bounds().unwrap());
+
+    assert_eq!((w, h), (160, 1));
+    assert_eq!(rgba.len(), 160 * 4);
+    assert_ne!(rgba[3], 0);
+    assert_ne!(rgba[159 * 4 + 3], 0);
+}
+
+#[test]
+fn removing_unlinks_instances() {
+    let cells = Cells::from_ranges((0..2, 0..2));
+    let mut scene = Scene::new();
+    scene.add_floor(Cracks1, North, (0..2, 0..2));
+    let prefab = Prefab::new("../rooms/small hall", &cells, scene).unwrap();
+    assert_eq!(prefab.name, "roomssmall_hall");
+
+    let mut scene = Scene::new();
+    scene.stamp(&prefab, [10, 0].into(), true);
+    scene.stamp(&prefab, [20, 0].into(), true);
+    scene.remove(&Cells::from_ranges((11..12, 1..2)), Show::new());
+
+    assert_eq!(scene.instances.len(), 1);
+    assert_eq!(scene.instances[0].delta, [20, 0].into());
+}
+
+// A banner under the left cell of a 2×1 region, with a side on its left
+fn transformed_walls(transform: Transform) -> Scene {
+    let mut scene = Scene::new();
+    scene.walls.insert(
+        [0, 0].into(),
+        Walls::new(Some(WallEnum::RedBanner), true, false),
+    );
+
+    scene.transformed(&(0..2, 0..1), transform)
+}
+
+fn assert_walls(scene: &Scene, walls: &[([i16; 2], Walls)]) {
+    assert_eq!(scene.walls.len(), walls.len());
+    for &(pos, expected) in walls {
+        assert_eq!(scene.walls.get(&pos.into()), Some(&expected));
+    }
+}
+
+#[test]
+fn painting_and_pasting_unlink_instances() {
+    let cells = Cells::from_ranges((0..2, 0..2));
+    let mut scene = Scene::new();
+    scene.add_floor(Cracks1, North, (0..2, 0..2));
+    let prefab = Prefab::new("hall", &cells, scene).unwrap();
+
+    let mut scene = Scene::new();
+    for x in &[10, 20, 30] {
+        scene.stamp(&prefab, [*x, 0].into(), true);
+    }
+    let pencil = Pencil::Floor((Floor, North));
+    let cells = Cells::from_ranges((11..12, 1..2));
+    scene.paint(&pencil, &cells, &Prefabs::default(), false);
+    let mut pasted = Scene::new();
+    pasted.add_floor(Floor, North, (0..1, 0..1));
+    scene.paste(pasted, [21, 0]);
+
+    assert_eq!(scene.instances.len(), 1);
+    assert_eq!(scene.instances[0].delta, [30, 0].into());
+}
+
+#[test]
+fn rotate_walls_90() {
+    assert_walls(&transformed_walls(Transform::Rotate90), &[
+        ([0, 0], Walls::with_left(true)),
+        ([0, -1], Walls::with_bottom(Some(WallEnum::Wall))),
+    ]);
+}
+
+#[test]
+fn rotate_walls_180() {
+    assert_walls(&transformed_walls(Transform::Rotate180), &[
+        ([1, 0], Walls::with_right(true)),
+        ([1, -1], Walls::with_bottom(Some(WallEnum::RedBanner))),
+    ]);
+}
+
+#[test]
+fn rotate_walls_270() {
+    assert_walls(&transformed_walls(Transform::Rotate270), &[(
+        [0, 1],
+        Walls::new(Some(WallEnum::Wall), false, true),
+    )]);
+}
+
+#[test]
+fn mirror_walls_horizontal() {
+    assert_walls(&transformed_walls(Transform::MirrorHorizontal), &[(
+        [1, 0],
+        Walls::new(Some(WallEnum::RedBanner), false, true),
+    )]);
+}
+
+#[test]
+fn mirror_walls_vertical() {
+    assert_walls(&transformed_walls(Transform::MirrorVertical), &[
+        ([0, 0], Walls::with_left(true)),
+        ([0, -1], Walls::with_bottom(Some(WallEnum::RedBanner))),
+    ]);
+}
+
+#[test]
+fn scene_bookmarks_round_trip() {
+    let mut scene = Scene::new();
+    scene.bookmarks.insert(3, Bookmark {
+        center: [12., -40.5].into(),
+        scale:  2.,
+    });
+    let text = scene.to_text();
+
+    assert_eq!(Scene::from_text(&text).unwrap().bookmarks, scene.bookmarks);
+}
+
+#[test]
+fn scene_tileset() {
+    let mut scene = Scene::new();
+    assert_eq!(scene.tileset(), TILESET);
+    assert!(!scene.to_text().contains("tileset"));
+
+    scene.tileset = Some(String::from("crypt"));
+    assert_eq!(
+        Scene::from_text(&scene.to_text()).unwrap().tileset(),
+        "crypt"
+    );
+}
+
+#[test]
+fn catalogue_parse() {
+    let catalogue = Catalogue::parse(
+        "floor 16 64 16 16\n\natlas 1 # second image\ncoin_anim 288 272 8 8 4\nbad 1 2\n",
+        "test.txt",
+    );
+
+    assert_eq!(catalogue.get("FLOOR"), Some(Tile::new(16, 64, 16, 16)));
+    assert_eq!(
+        catalogue.get("COIN_ANIM_2"),
+        Some(Tile {
+            atlas: 1,
+            ..Tile::new(304, 272, 8, 8)
+        })
+    );
+    assert_eq!(catalogue.get("COIN_ANIM_4"), None);
+    assert_eq!(catalogue.get("BAD"), None);
+    assert_eq!(catalogue.atlases(), 2);
+    assert_eq!(Catalogue::default_tileset().cell(), [16., 16.].into());
+    assert_eq!(
+        Catalogue::parse("cell 32 24\n", "test.txt").cell(),
+        [32., 24.].into()
+    );
+    assert_eq!(
+        Catalogue::default_tileset().tile(FloorEnum::Floor),
+        Tile::new(16, 64, 16, 16)
+    );
+}
+
+#[test]
+fn element_tiles_resolve() {
+    let catalogue = Catalogue::default_tileset();
+
+    for (_, elements) in Element::categories().iter() {
+        for &element in elements {
+            assert_ne!(catalogue.tile(element), Tile::EMPTY, "{}", element.name());
+        }
+    }
+    for &side in WallSideEnum::all() {
+        assert_ne!(catalogue.tile(side), Tile::EMPTY, "{}", side.name());
+    }
+    assert!(catalogue.missing().is_empty());
+}
+
+#[test]
+fn reload_changed_scene() {
+    let path = std::env::temp_dir().join(format!("rpg-reload-{}.txt", std::process::id()));
+    let path = path.to_str().unwrap();
+    let write = |floors: &[(i16, i16)]| {
+        let mut scene = Scene::new();
+        for &(x, y) in floors {
+            scene
+                .floors
+                .insert([x, y].into(), (FloorEnum::Floor, North));
+        }
+        std::fs::write(path, scene.to_text()).unwrap();
+    };
+
+    // Checked on every reload, the sizes telling the writes apart
+    let mut scene_view = SceneView::new(SIZE.into());
+    scene_view.set_watch_interval(Duration::from_secs(0));
+    write(&[(0, 0)]);
+    scene_view.open(path);
+    assert_eq!(scene_view.scene().floors.len(), 1);
+
+    // Unmodified, the scene follows the file
+    write(&[(0, 0), (1, 0)]);
+    scene_view.reload();
+    assert_eq!(scene_view.scene().floors.len(), 2);
+    assert!(!scene_view.is_typing());
+
+    // Edited, it waits for an answer
+    scene_view.menu_item(MenuItem::Delete, [0, 0].into());
+    write(&[(0, 0), (1, 0), (2, 0)]);
+    scene_view.reload();
+    assert_eq!(scene_view.scene().floors.len(), 1);
+    assert!(scene_view.is_typing());
+    assert_eq!(
+        scene_view.status().last_message(),
+        Some(&*format!("Reloaded {}", path))
+    );
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn frame_text_round_trip() {
+    let frame = Frame {
+        time:     Duration::from_millis(1234),
+        size:     SIZE.into(),
+        keys:     vec![KeyCode::A, KeyCode::Up],
+        mods:     KeyMods::CTRL | KeyMods::SHIFT,
+        chars:    vec!['a', ' ', 'é'],
+        position: [10.5, 20.].into(),
+        left:     true,
+        middle:   false,
+        right:    true,
+        wheel:    vec![[0., -1.].into()],
+        gamepad:  vec![
+            GamepadEvent::Down(Button::South),
+            GamepadEvent::Up(Button::South),
+            GamepadEvent::Axis(Axis::LeftStickX, -0.25),
+        ],
+    };
+
+    assert_eq!(Frame::from_text(&frame.to_text()), Some(frame));
+}